@@ -16,6 +16,8 @@ lazy_static::lazy_static! {
     pub static ref DEFAULT_DB_PATH: PathBuf = dirs::home_dir().unwrap().join(".fuel").join("db");
 }
 
+pub mod db;
+pub mod regenesis;
 pub mod run;
 pub mod snapshot;
 
@@ -36,6 +38,8 @@ pub struct Opt {
 pub enum Fuel {
     Run(run::Command),
     Snapshot(snapshot::Command),
+    Regenesis(regenesis::Command),
+    Db(db::Command),
 }
 
 pub const LOG_FILTER: &str = "RUST_LOG";
@@ -100,6 +104,8 @@ pub async fn run_cli() -> anyhow::Result<()> {
         Ok(opt) => match opt.command {
             Fuel::Run(command) => run::exec(command).await,
             Fuel::Snapshot(command) => snapshot::exec(command).await,
+            Fuel::Regenesis(command) => regenesis::exec(command).await,
+            Fuel::Db(command) => db::exec(command).await,
         },
         Err(e) => {
             // Prints the error and exits.
@@ -0,0 +1,125 @@
+use crate::cli::DEFAULT_DB_PATH;
+use clap::{
+    Parser,
+    Subcommand,
+};
+use std::path::PathBuf;
+
+/// Maintenance of the database.
+#[derive(Debug, Clone, Parser)]
+pub struct Command {
+    /// The path to the database.
+    #[clap(
+        name = "DB_PATH",
+        long = "db-path",
+        value_parser,
+        default_value = (*DEFAULT_DB_PATH).to_str().unwrap()
+    )]
+    database_path: PathBuf,
+
+    /// Specify either an alias to a built-in configuration or filepath to a JSON file.
+    /// The database must belong to the chain of this configuration.
+    #[clap(name = "CHAIN_CONFIG", long = "chain", default_value = "local_testnet")]
+    chain_config: String,
+
+    /// The sub-command of the database operation.
+    #[command(subcommand)]
+    subcommand: SubCommands,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum SubCommands {
+    /// Creates a consistent backup of the stopped node's database.
+    /// Use the `createDatabaseCheckpoint` GraphQL mutation to back up a running node.
+    #[command(arg_required_else_help = true)]
+    Backup {
+        /// The directory of the backup. It must not exist.
+        #[clap(long = "to")]
+        to: PathBuf,
+    },
+    /// Restores the database from the backup. The database must not exist.
+    #[command(arg_required_else_help = true)]
+    Restore {
+        /// The directory of the backup.
+        #[clap(long = "from")]
+        from: PathBuf,
+    },
+    /// Verifies the internal consistency of the stopped node's database: the Merkle
+    /// roots of the contracts and blocks, the owner indexes and the block height index.
+    Verify,
+}
+
+#[cfg(not(any(feature = "rocksdb", feature = "rocksdb-production")))]
+pub async fn exec(command: Command) -> anyhow::Result<()> {
+    Err(anyhow::anyhow!(
+        "Rocksdb must be enabled to use the database at {}",
+        command.database_path.display()
+    ))
+}
+
+#[cfg(any(feature = "rocksdb", feature = "rocksdb-production"))]
+pub async fn exec(command: Command) -> anyhow::Result<()> {
+    use crate::cli::init_logging;
+    use anyhow::Context;
+    use fuel_core::{
+        chain_config::ChainConfig,
+        database::Database,
+    };
+    use tracing::{
+        error,
+        info,
+    };
+    init_logging().await?;
+    let path = command.database_path;
+    let config: ChainConfig = command.chain_config.parse()?;
+
+    match command.subcommand {
+        SubCommands::Backup { to } => {
+            if !path.is_dir() {
+                anyhow::bail!("The database {} doesn't exist", path.display());
+            }
+            let database = Database::open(&path, None).context(format!(
+                "failed to open database at path {}",
+                path.display()
+            ))?;
+            database.verify_metadata(&config.chain_name)?;
+            database.backup(&to)?;
+            info!(
+                "The database {} is backed up to {}",
+                path.display(),
+                to.display()
+            );
+        }
+        SubCommands::Verify => {
+            if !path.is_dir() {
+                anyhow::bail!("The database {} doesn't exist", path.display());
+            }
+            let database = Database::open(&path, None).context(format!(
+                "failed to open database at path {}",
+                path.display()
+            ))?;
+            database.verify_metadata(&config.chain_name)?;
+            let inconsistencies = database.verify_integrity()?;
+            for inconsistency in &inconsistencies {
+                error!("{}", inconsistency);
+            }
+            if !inconsistencies.is_empty() {
+                anyhow::bail!(
+                    "The database {} has {} inconsistencies",
+                    path.display(),
+                    inconsistencies.len()
+                );
+            }
+            info!("The database {} is consistent", path.display());
+        }
+        SubCommands::Restore { from } => {
+            Database::restore(&from, &path, &config.chain_name, None)?;
+            info!(
+                "The database {} is restored from {}",
+                path.display(),
+                from.display()
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use crate::cli::{
+    snapshot::Format,
+    DEFAULT_DB_PATH,
+};
+use clap::{
+    Parser,
+    Subcommand,
+};
+use std::path::PathBuf;
+
+/// The name of the chain config written by the export.
+const CHAIN_CONFIG_FILE: &str = "chain_config.json";
+
+/// Starts a new chain from the state of the stopped one. The first block of the new
+/// chain follows the last block of the stopped chain and links to its blocks.
+#[derive(Debug, Clone, Parser)]
+pub struct Command {
+    /// The sub-command of the regenesis operation.
+    #[command(subcommand)]
+    subcommand: SubCommands,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum SubCommands {
+    /// Exports the chain config and the genesis state of the new chain. The new chain
+    /// is started by `fuel-core run` with the `--chain` and `--genesis-state`
+    /// of the exported files.
+    Export {
+        /// The path to the database of the stopped chain.
+        #[clap(
+            name = "DB_PATH",
+            long = "db-path",
+            value_parser,
+            default_value = (*DEFAULT_DB_PATH).to_str().unwrap()
+        )]
+        database_path: PathBuf,
+        /// The chain config of the stopped chain. Either an alias to a built-in
+        /// configuration or filepath to a JSON file.
+        #[clap(long = "chain", default_value = "local_testnet")]
+        chain_config: String,
+        /// The directory to write the chain config and the genesis state into.
+        #[clap(long = "output-dir", value_parser)]
+        output_dir: PathBuf,
+        /// The encoding of the genesis state.
+        #[clap(long = "format", value_enum, default_value = "json")]
+        format: Format,
+    },
+    /// Verifies that the genesis of the new chain follows the last block of the
+    /// stopped chain and that its state roots match the state of the stopped chain.
+    Verify {
+        /// The path to the database of the new chain.
+        #[clap(
+            name = "DB_PATH",
+            long = "db-path",
+            value_parser,
+            default_value = (*DEFAULT_DB_PATH).to_str().unwrap()
+        )]
+        database_path: PathBuf,
+        /// The path to the database of the stopped chain.
+        #[clap(long = "source-db-path", value_parser)]
+        source_database_path: PathBuf,
+    },
+}
+
+#[cfg(not(any(feature = "rocksdb", feature = "rocksdb-production")))]
+pub async fn exec(_: Command) -> anyhow::Result<()> {
+    Err(anyhow::anyhow!(
+        "Rocksdb must be enabled to use the regenesis"
+    ))
+}
+
+#[cfg(any(feature = "rocksdb", feature = "rocksdb-production"))]
+pub async fn exec(command: Command) -> anyhow::Result<()> {
+    use crate::cli::init_logging;
+    use anyhow::Context;
+    use fuel_core::{
+        chain_config::{
+            ChainConfig,
+            StateEncoding,
+            StateWriter,
+        },
+        database::Database,
+        service::genesis::verify_regenesis,
+    };
+    use std::{
+        fs::File,
+        io::BufWriter,
+        path::Path,
+    };
+
+    fn open(path: &Path) -> anyhow::Result<Database> {
+        let data_source =
+            fuel_core::state::rocks_db::RocksDb::default_open(path, None).context(
+                format!("failed to open database at path {}", path.display()),
+            )?;
+        Ok(Database::new(std::sync::Arc::new(data_source)))
+    }
+
+    init_logging().await?;
+    match command.subcommand {
+        SubCommands::Export {
+            database_path,
+            chain_config,
+            output_dir,
+            format,
+        } => {
+            let db = open(&database_path)?;
+            let chain_config: ChainConfig = chain_config.parse()?;
+            let (encoding, state_file) = match format {
+                Format::Json => (StateEncoding::Json, "genesis_state.json"),
+                Format::Binary => (StateEncoding::Binary, "genesis_state.bin"),
+            };
+            std::fs::create_dir_all(&output_dir).context(format!(
+                "failed to create the directory {}",
+                output_dir.display()
+            ))?;
+
+            // The state is imported from the genesis state file instead.
+            let chain_config = ChainConfig {
+                initial_state: None,
+                ..chain_config
+            };
+            let chain_config_path = output_dir.join(CHAIN_CONFIG_FILE);
+            let file = File::create(&chain_config_path).context(format!(
+                "failed to create the chain config {}",
+                chain_config_path.display()
+            ))?;
+            serde_json::to_writer_pretty(file, &chain_config)
+                .context("failed to write the chain config")?;
+
+            let state_path = output_dir.join(state_file);
+            let file = File::create(&state_path).context(format!(
+                "failed to create the genesis state {}",
+                state_path.display()
+            ))?;
+            let mut writer = StateWriter::with_encoding(BufWriter::new(file), encoding)?;
+            for entry in db.regenesis_snapshot()? {
+                writer.write(&entry?)?;
+            }
+            writer
+                .finish()
+                .context("failed to write the genesis state")?;
+
+            tracing::info!(
+                "Exported the regenesis, start the new chain with `--chain {} \
+                --genesis-state {}`",
+                chain_config_path.display(),
+                state_path.display()
+            );
+        }
+        SubCommands::Verify {
+            database_path,
+            source_database_path,
+        } => {
+            let db = open(&database_path)?;
+            let source = open(&source_database_path)?;
+            verify_regenesis(&source, &db)?;
+            tracing::info!("The genesis of the new chain matches the source chain");
+        }
+    }
+    Ok(())
+}
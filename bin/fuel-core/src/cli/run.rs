@@ -20,12 +20,15 @@ use fuel_core::{
     producer::Config as ProducerConfig,
     service::{
         config::Trigger,
+        pruner::Config as PrunerConfig,
+        secondary::Config as SecondaryConfig,
         Config,
         DbType,
         RelayerVerifierConfig,
         ServiceTrait,
         VMConfig,
     },
+    state::rocks_db_config::RocksDbConfig,
     txpool::Config as TxPoolConfig,
     types::{
         blockchain::primitives::SecretKeyWrapper,
@@ -100,6 +103,49 @@ pub struct Command {
     )]
     pub database_type: DbType,
 
+    /// The path to the TOML file with the tuning of the database column families:
+    /// the block cache share, bloom filter, compression, write buffer size and
+    /// compaction style of each column.
+    #[arg(long = "db-config", value_parser, env)]
+    pub db_config: Option<PathBuf>,
+
+    /// Lists pending database migrations, applies them to a temporary checkpoint
+    /// of the database and exits. The database itself is not modified.
+    /// Can be used to check that the database is compatible with this version of the node.
+    #[arg(long = "db-migration-dry-run", env)]
+    pub db_migration_dry_run: bool,
+
+    /// The number of the latest blocks whose transactions, receipts and statuses are kept.
+    /// The data of older blocks is pruned in the background. All blocks are kept if not set.
+    #[arg(
+        long = "retained-blocks",
+        value_parser = clap::value_parser!(u32).range(1..),
+        env
+    )]
+    pub retained_blocks: Option<u32>,
+
+    /// Record the historical state of the contracts, coins and messages to allow
+    /// querying it at the past block heights. The history starts from the latest block
+    /// at the moment of enabling and is discarded when the node starts without the flag.
+    #[arg(long = "archive", env)]
+    pub archive: bool,
+
+    /// The directory for the database checkpoints created by the `createDatabaseCheckpoint`
+    /// GraphQL mutation. The mutation is disabled if not set.
+    #[arg(long = "db-checkpoint-dir", value_parser, env)]
+    pub db_checkpoint_dir: Option<PathBuf>,
+
+    /// Serve only the GraphQL queries from the read-only secondary instance of the database
+    /// at the `--db-path`, written by another node running on the same machine.
+    /// The block production, the transaction pool and the networking are disabled.
+    #[arg(long = "db-read-only-secondary", env)]
+    pub db_read_only_secondary: bool,
+
+    /// How often the read-only secondary instance applies the new writes of the node
+    /// that owns the database.
+    #[arg(long = "db-secondary-catch-up-interval", default_value = "500ms", env)]
+    pub db_secondary_catch_up_interval: humantime::Duration,
+
     /// Specify either an alias to a built-in configuration or filepath to a JSON file.
     #[arg(
         name = "CHAIN_CONFIG",
@@ -109,6 +155,12 @@ pub struct Command {
     )]
     pub chain_config: String,
 
+    /// The file with the genesis state streamed one JSON entry per line, as written
+    /// by the `snapshot everything` command. It replaces the initial state of the
+    /// chain config and is imported in batches, so it may be larger than the memory.
+    #[arg(long = "genesis-state", value_parser, env)]
+    pub genesis_state: Option<PathBuf>,
+
     /// Allows GraphQL Endpoints to arbitrarily advanced blocks. Should be used for local development only
     #[arg(long = "manual_blocks_enabled", env)]
     pub manual_blocks_enabled: bool,
@@ -201,7 +253,15 @@ impl Command {
             max_database_cache_size,
             database_path,
             database_type,
+            db_config,
+            db_migration_dry_run: _,
+            retained_blocks,
+            archive,
+            db_checkpoint_dir,
+            db_read_only_secondary,
+            db_secondary_catch_up_interval,
             chain_config,
+            genesis_state,
             vm_backtrace,
             manual_blocks_enabled,
             utxo_validation,
@@ -284,7 +344,9 @@ impl Command {
             max_database_cache_size,
             database_path,
             database_type,
+            database_config: load_database_config(db_config)?,
             chain_conf: chain_conf.clone(),
+            genesis_state,
             utxo_validation,
             manual_blocks_enabled,
             block_production: trigger,
@@ -308,6 +370,12 @@ impl Command {
             },
             block_executor: Default::default(),
             block_importer: Default::default(),
+            block_pruning: retained_blocks.map(PrunerConfig::new),
+            archive,
+            read_only_secondary: db_read_only_secondary.then(|| SecondaryConfig {
+                catch_up_interval: db_secondary_catch_up_interval.into(),
+            }),
+            database_checkpoint_dir: db_checkpoint_dir,
             #[cfg(feature = "relayer")]
             relayer: relayer_args.into(),
             #[cfg(feature = "p2p")]
@@ -325,6 +393,9 @@ impl Command {
 
 pub async fn exec(command: Command) -> anyhow::Result<()> {
     init_logging().await?;
+    if command.db_migration_dry_run {
+        return migration_dry_run(&command)
+    }
     let config = command.get_config()?;
     let network_name = {
         #[cfg(feature = "p2p")]
@@ -356,6 +427,88 @@ pub async fn exec(command: Command) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(not(any(feature = "rocksdb", feature = "rocksdb-production")))]
+fn migration_dry_run(command: &Command) -> anyhow::Result<()> {
+    Err(anyhow!(
+        "Rocksdb must be enabled to migrate the database at {}",
+        command.database_path.display()
+    ))
+}
+
+#[cfg(any(feature = "rocksdb", feature = "rocksdb-production"))]
+fn migration_dry_run(command: &Command) -> anyhow::Result<()> {
+    use fuel_core::database::Database;
+
+    if command.database_type != DbType::RocksDb {
+        return Err(anyhow!(
+            "The migration dry run requires the `{}` database type",
+            DbType::RocksDb
+        ))
+    }
+
+    let chain_conf: ChainConfig = command.chain_config.as_str().parse()?;
+    let database =
+        Database::open(&command.database_path, command.max_database_cache_size)?;
+    match database.get_chain_name()? {
+        Some(chain_name) if chain_name == chain_conf.chain_name => {}
+        Some(chain_name) => {
+            return Err(anyhow!(
+                "The database at {} belongs to the chain `{}`, but `{}` is configured",
+                command.database_path.display(),
+                chain_name,
+                chain_conf.chain_name
+            ))
+        }
+        None => {
+            return Err(anyhow!(
+                "The database at {} is not initialized, there is nothing to migrate",
+                command.database_path.display()
+            ))
+        }
+    }
+
+    let pending = database.pending_migrations()?;
+    if pending.is_empty() {
+        info!("The database is up to date, there are no pending migrations");
+        return Ok(())
+    }
+    for migration in pending {
+        info!(
+            "Pending migration {:#x}: {}",
+            migration.version, migration.description
+        );
+    }
+    // The dry run works with a checkpoint of the database, so the database must be closed.
+    drop(database);
+
+    let applied = Database::dry_run_migrations(
+        &command.database_path,
+        Some(command.max_database_cache_size),
+    )?;
+    info!(
+        "Dry run of {} migrations succeeded, the database is not modified",
+        applied.len()
+    );
+    Ok(())
+}
+
+fn load_database_config(path: Option<PathBuf>) -> anyhow::Result<RocksDbConfig> {
+    let Some(path) = path else {
+        return Ok(Default::default())
+    };
+    let content = std::fs::read_to_string(&path).context(format!(
+        "failed to read the database config {}",
+        path.display()
+    ))?;
+    let config: RocksDbConfig = toml::from_str(&content).context(format!(
+        "failed to parse the database config {}",
+        path.display()
+    ))?;
+    // Fail before the start of the node if the columns are misconfigured.
+    config.column_configs()?;
+    Ok(config)
+}
+
 // Attempt to load the consensus key from cli arg first, otherwise check the env.
 fn load_consensus_key(
     cli_arg: Option<String>,
@@ -404,3 +557,43 @@ async fn shutdown_signal() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core::state::rocks_db_config::Compression;
+    use std::io::Write;
+
+    #[test]
+    fn database_config_is_loaded_from_toml() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"
+            block_cache_size = 1048576
+
+            [columns.ContractsState]
+            block_cache_share = 0.5
+
+            [columns.Receipts]
+            compression = "zstd"
+            "#
+        )
+        .unwrap();
+
+        let config = load_database_config(Some(file.path().to_path_buf())).unwrap();
+        assert_eq!(config.block_cache_size, Some(1048576));
+        assert_eq!(
+            config.columns["Receipts"].compression,
+            Some(Compression::Zstd)
+        );
+    }
+
+    #[test]
+    fn database_config_with_unknown_column_is_rejected() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "[columns.Unknown]").unwrap();
+
+        assert!(load_database_config(Some(file.path().to_path_buf())).is_err());
+    }
+}
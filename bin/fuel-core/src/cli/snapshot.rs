@@ -2,8 +2,13 @@ use crate::cli::DEFAULT_DB_PATH;
 use clap::{
     Parser,
     Subcommand,
+    ValueEnum,
+};
+use fuel_core::types::fuel_types::{
+    Address,
+    AssetId,
+    ContractId,
 };
-use fuel_core::types::fuel_types::ContractId;
 use std::path::PathBuf;
 
 /// Print a snapshot of blockchain state to stdout.
@@ -25,12 +30,26 @@ pub struct Command {
 
 #[derive(Debug, Clone, Subcommand)]
 pub enum SubCommands {
-    /// Creates a snapshot of the entire database and produces a chain config.
-    #[command(arg_required_else_help = true)]
+    /// Creates a snapshot of the entire state of the database, streamed entry by entry.
+    /// The snapshot can be used as the `--genesis-state` of a new node.
     Everything {
-        /// Specify either an alias to a built-in configuration or filepath to a JSON file.
-        #[clap(name = "CHAIN_CONFIG", long = "chain", default_value = "local_testnet")]
-        chain_config: String,
+        /// The height of the block after which the state is taken. The latest state
+        /// is used if not set. The past state is available only in the archive mode.
+        #[clap(long = "height")]
+        height: Option<u32>,
+        /// Include only the coins and the messages of the owner. Can be repeated.
+        #[clap(long = "owner")]
+        owners: Vec<Address>,
+        /// Include only the coins and the contract balances of the asset.
+        /// Can be repeated.
+        #[clap(long = "asset-id")]
+        asset_ids: Vec<AssetId>,
+        /// Include only the contract. Can be repeated.
+        #[clap(long = "contract-id")]
+        contract_ids: Vec<ContractId>,
+        /// The encoding of the snapshot.
+        #[clap(long = "format", value_enum, default_value = "json")]
+        format: Format,
     },
     /// Creates a config for the contract.
     #[command(arg_required_else_help = true)]
@@ -41,6 +60,14 @@ pub enum SubCommands {
     },
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One JSON object per line.
+    Json,
+    /// The compact binary encoding.
+    Binary,
+}
+
 #[cfg(not(any(feature = "rocksdb", feature = "rocksdb-production")))]
 pub async fn exec(command: Command) -> anyhow::Result<()> {
     Err(anyhow::anyhow!(
@@ -55,10 +82,13 @@ pub async fn exec(command: Command) -> anyhow::Result<()> {
     use anyhow::Context;
     use fuel_core::{
         chain_config::{
-            ChainConfig,
-            StateConfig,
+            StateEncoding,
+            StateWriter,
+        },
+        database::{
+            snapshot::SnapshotFilter,
+            Database,
         },
-        database::Database,
     };
     init_logging().await?;
     let path = command.database_path;
@@ -69,19 +99,30 @@ pub async fn exec(command: Command) -> anyhow::Result<()> {
     let db = Database::new(std::sync::Arc::new(data_source));
 
     match command.subcommand {
-        SubCommands::Everything { chain_config } => {
-            let config: ChainConfig = chain_config.parse()?;
-            let state_conf = StateConfig::generate_state_config(db)?;
-
-            let chain_conf = ChainConfig {
-                initial_state: Some(state_conf),
-                ..config
+        SubCommands::Everything {
+            height,
+            owners,
+            asset_ids,
+            contract_ids,
+            format,
+        } => {
+            let filter = SnapshotFilter {
+                owners,
+                asset_ids,
+                contract_ids,
             };
+            let encoding = match format {
+                Format::Json => StateEncoding::Json,
+                Format::Binary => StateEncoding::Binary,
+            };
+            let entries = db.state_snapshot(height.map(Into::into), filter)?;
 
-            let stdout = std::io::stdout().lock();
-
-            serde_json::to_writer_pretty(stdout, &chain_conf)
-                .context("failed to dump snapshot to JSON")?;
+            let stdout = std::io::BufWriter::new(std::io::stdout().lock());
+            let mut writer = StateWriter::with_encoding(stdout, encoding)?;
+            for entry in entries {
+                writer.write(&entry?)?;
+            }
+            writer.finish().context("failed to dump snapshot")?;
         }
         SubCommands::Contract { contract_id } => {
             let config = db.get_contract_config_by_id(contract_id)?;
@@ -1,6 +1,9 @@
 use crate::serialization::HexNumber;
 
-use fuel_core_storage::Result as StorageResult;
+use fuel_core_storage::{
+    iter::BoxedIter,
+    Result as StorageResult,
+};
 use fuel_core_types::fuel_types::BlockHeight;
 
 use serde::{
@@ -16,9 +19,11 @@ use super::{
     coin::CoinConfig,
     contract::ContractConfig,
     message::MessageConfig,
+    stream::StateEntry,
 };
 
-// TODO: do streaming deserialization to handle large state configs
+/// The state of the chain loaded into memory at once.
+/// The large states should use the streamed format, see [`StateEntry`].
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
@@ -58,4 +63,6 @@ pub trait ChainConfigDb {
     fn get_message_config(&self) -> StorageResult<Option<Vec<MessageConfig>>>;
     /// Returns the last available block height.
     fn get_block_height(&self) -> StorageResult<BlockHeight>;
+    /// Returns the entries of the whole state one by one, starting from the height.
+    fn state_entries(&self) -> BoxedIter<'_, StorageResult<StateEntry>>;
 }
@@ -0,0 +1,614 @@
+//! The streamed format of the state snapshot.
+//!
+//! The snapshot is a sequence of [`StateEntry`], one JSON object per line or
+//! length-prefixed binary entries, see [`StateEncoding`]. Unlike the [`StateConfig`],
+//! it can be written and read entry by entry, so the size of the state is not limited
+//! by the available memory.
+
+use crate::{
+    serialization::{
+        HexNumber,
+        HexType,
+    },
+    CoinConfig,
+    ContractConfig,
+    MessageConfig,
+    StateConfig,
+};
+use anyhow::Context;
+use fuel_core_types::{
+    blockchain::primitives::DaBlockHeight,
+    fuel_asm::Word,
+    fuel_types::{
+        Address,
+        AssetId,
+        BlockHeight,
+        Bytes32,
+        ContractId,
+        Nonce,
+        Salt,
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_with::serde_as;
+use std::io::{
+    BufRead,
+    Write,
+};
+
+/// The header of the binary snapshot. The JSON snapshot never starts with zero.
+const BINARY_MAGIC: &[u8] = b"\0fuel-state-v1\0";
+
+/// The encoding of the streamed state snapshot.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StateEncoding {
+    /// One JSON object per line.
+    #[default]
+    Json,
+    /// The header followed by the postcard encoded entries, each prefixed by
+    /// its length as a little-endian `u32`.
+    Binary,
+}
+
+/// The entry of the streamed state snapshot.
+///
+/// The [`StateEntry::Height`] or the [`StateEntry::LastBlock`] goes first, if any.
+/// The state and balances of the contract
+/// may be embedded into the [`StateEntry::Contract`] or follow it as separate
+/// [`StateEntry::ContractState`] and [`StateEntry::ContractBalance`] entries.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StateEntry {
+    /// Starting block height of the state.
+    Height(#[serde_as(as = "HexNumber")] BlockHeight),
+    Coin(CoinConfig),
+    Contract(ContractConfig),
+    ContractState(ContractStateConfig),
+    ContractBalance(ContractBalanceConfig),
+    Message(MessageConfig),
+    /// The last block of the previous chain, continued by the chain starting with
+    /// this state after the regenesis. The genesis block follows it.
+    LastBlock(LastBlockConfig),
+}
+
+/// The storage slot of the contract.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct ContractStateConfig {
+    #[serde_as(as = "HexType")]
+    pub contract_id: ContractId,
+    #[serde_as(as = "HexType")]
+    pub key: Bytes32,
+    #[serde_as(as = "HexType")]
+    pub value: Bytes32,
+}
+
+/// The balance of the contract.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct ContractBalanceConfig {
+    #[serde_as(as = "HexType")]
+    pub contract_id: ContractId,
+    #[serde_as(as = "HexType")]
+    pub asset_id: AssetId,
+    #[serde_as(as = "HexNumber")]
+    pub amount: Word,
+}
+
+/// The last block of the chain the state is taken from.
+#[serde_as]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct LastBlockConfig {
+    #[serde_as(as = "HexNumber")]
+    pub height: BlockHeight,
+    /// The root of the Merkle tree of the blocks up to and including the last one.
+    #[serde_as(as = "HexType")]
+    pub blocks_root: Bytes32,
+}
+
+impl LastBlockConfig {
+    /// The height of the genesis block of the chain continuing this one.
+    pub fn genesis_height(&self) -> anyhow::Result<BlockHeight> {
+        u32::from(self.height)
+            .checked_add(1)
+            .map(Into::into)
+            .ok_or_else(|| anyhow::anyhow!("the last block height is the maximum"))
+    }
+}
+
+/// The [`StateEntry`] in the binary encoding. The fields are encoded as is, without
+/// the hex strings and the skipped empty values of the JSON.
+#[derive(Deserialize, Serialize)]
+enum BinaryEntry {
+    Height(BlockHeight),
+    Coin {
+        tx_id: Option<Bytes32>,
+        output_index: Option<u8>,
+        tx_pointer_block_height: Option<BlockHeight>,
+        tx_pointer_tx_idx: Option<u16>,
+        maturity: Option<BlockHeight>,
+        owner: Address,
+        amount: u64,
+        asset_id: AssetId,
+    },
+    Contract {
+        contract_id: ContractId,
+        code: Vec<u8>,
+        salt: Salt,
+        state: Option<Vec<(Bytes32, Bytes32)>>,
+        balances: Option<Vec<(AssetId, u64)>>,
+        tx_id: Option<Bytes32>,
+        output_index: Option<u8>,
+        tx_pointer_block_height: Option<BlockHeight>,
+        tx_pointer_tx_idx: Option<u16>,
+    },
+    ContractState {
+        contract_id: ContractId,
+        key: Bytes32,
+        value: Bytes32,
+    },
+    ContractBalance {
+        contract_id: ContractId,
+        asset_id: AssetId,
+        amount: Word,
+    },
+    Message {
+        sender: Address,
+        recipient: Address,
+        nonce: Nonce,
+        amount: Word,
+        data: Vec<u8>,
+        da_height: DaBlockHeight,
+    },
+    LastBlock {
+        height: BlockHeight,
+        blocks_root: Bytes32,
+    },
+}
+
+impl From<StateEntry> for BinaryEntry {
+    fn from(entry: StateEntry) -> Self {
+        match entry {
+            StateEntry::Height(height) => BinaryEntry::Height(height),
+            StateEntry::Coin(CoinConfig {
+                tx_id,
+                output_index,
+                tx_pointer_block_height,
+                tx_pointer_tx_idx,
+                maturity,
+                owner,
+                amount,
+                asset_id,
+            }) => BinaryEntry::Coin {
+                tx_id,
+                output_index,
+                tx_pointer_block_height,
+                tx_pointer_tx_idx,
+                maturity,
+                owner,
+                amount,
+                asset_id,
+            },
+            StateEntry::Contract(ContractConfig {
+                contract_id,
+                code,
+                salt,
+                state,
+                balances,
+                tx_id,
+                output_index,
+                tx_pointer_block_height,
+                tx_pointer_tx_idx,
+            }) => BinaryEntry::Contract {
+                contract_id,
+                code,
+                salt,
+                state,
+                balances,
+                tx_id,
+                output_index,
+                tx_pointer_block_height,
+                tx_pointer_tx_idx,
+            },
+            StateEntry::ContractState(ContractStateConfig {
+                contract_id,
+                key,
+                value,
+            }) => BinaryEntry::ContractState {
+                contract_id,
+                key,
+                value,
+            },
+            StateEntry::ContractBalance(ContractBalanceConfig {
+                contract_id,
+                asset_id,
+                amount,
+            }) => BinaryEntry::ContractBalance {
+                contract_id,
+                asset_id,
+                amount,
+            },
+            StateEntry::Message(MessageConfig {
+                sender,
+                recipient,
+                nonce,
+                amount,
+                data,
+                da_height,
+            }) => BinaryEntry::Message {
+                sender,
+                recipient,
+                nonce,
+                amount,
+                data,
+                da_height,
+            },
+            StateEntry::LastBlock(LastBlockConfig {
+                height,
+                blocks_root,
+            }) => BinaryEntry::LastBlock {
+                height,
+                blocks_root,
+            },
+        }
+    }
+}
+
+impl From<BinaryEntry> for StateEntry {
+    fn from(entry: BinaryEntry) -> Self {
+        match entry {
+            BinaryEntry::Height(height) => StateEntry::Height(height),
+            BinaryEntry::Coin {
+                tx_id,
+                output_index,
+                tx_pointer_block_height,
+                tx_pointer_tx_idx,
+                maturity,
+                owner,
+                amount,
+                asset_id,
+            } => StateEntry::Coin(CoinConfig {
+                tx_id,
+                output_index,
+                tx_pointer_block_height,
+                tx_pointer_tx_idx,
+                maturity,
+                owner,
+                amount,
+                asset_id,
+            }),
+            BinaryEntry::Contract {
+                contract_id,
+                code,
+                salt,
+                state,
+                balances,
+                tx_id,
+                output_index,
+                tx_pointer_block_height,
+                tx_pointer_tx_idx,
+            } => StateEntry::Contract(ContractConfig {
+                contract_id,
+                code,
+                salt,
+                state,
+                balances,
+                tx_id,
+                output_index,
+                tx_pointer_block_height,
+                tx_pointer_tx_idx,
+            }),
+            BinaryEntry::ContractState {
+                contract_id,
+                key,
+                value,
+            } => StateEntry::ContractState(ContractStateConfig {
+                contract_id,
+                key,
+                value,
+            }),
+            BinaryEntry::ContractBalance {
+                contract_id,
+                asset_id,
+                amount,
+            } => StateEntry::ContractBalance(ContractBalanceConfig {
+                contract_id,
+                asset_id,
+                amount,
+            }),
+            BinaryEntry::Message {
+                sender,
+                recipient,
+                nonce,
+                amount,
+                data,
+                da_height,
+            } => StateEntry::Message(MessageConfig {
+                sender,
+                recipient,
+                nonce,
+                amount,
+                data,
+                da_height,
+            }),
+            BinaryEntry::LastBlock {
+                height,
+                blocks_root,
+            } => StateEntry::LastBlock(LastBlockConfig {
+                height,
+                blocks_root,
+            }),
+        }
+    }
+}
+
+impl StateConfig {
+    /// Returns the entries of the state in the order of the streamed snapshot.
+    pub fn entries(&self) -> impl Iterator<Item = StateEntry> + '_ {
+        let height = self.height.map(StateEntry::Height);
+        let coins = self.coins.iter().flatten().cloned().map(StateEntry::Coin);
+        let contracts = self
+            .contracts
+            .iter()
+            .flatten()
+            .cloned()
+            .map(StateEntry::Contract);
+        let messages = self
+            .messages
+            .iter()
+            .flatten()
+            .cloned()
+            .map(StateEntry::Message);
+
+        height
+            .into_iter()
+            .chain(coins)
+            .chain(contracts)
+            .chain(messages)
+    }
+}
+
+/// Reads the streamed state snapshot entry by entry.
+/// The encoding of the snapshot is detected by its first byte.
+pub struct StateReader<R> {
+    reader: R,
+    encoding: Option<StateEncoding>,
+    line: String,
+    /// The number of the line of the JSON snapshot, or the number of the entry
+    /// of the binary snapshot.
+    position: usize,
+}
+
+impl<R: BufRead> StateReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            encoding: None,
+            line: String::new(),
+            position: 0,
+        }
+    }
+
+    fn read_entry(&mut self) -> anyhow::Result<Option<StateEntry>> {
+        let encoding = match self.encoding {
+            Some(encoding) => encoding,
+            None => {
+                let encoding = self.read_encoding()?;
+                self.encoding = Some(encoding);
+                encoding
+            }
+        };
+
+        match encoding {
+            StateEncoding::Json => self.read_json_entry(),
+            StateEncoding::Binary => self.read_binary_entry(),
+        }
+    }
+
+    fn read_encoding(&mut self) -> anyhow::Result<StateEncoding> {
+        let buffer = self
+            .reader
+            .fill_buf()
+            .context("failed to read the state snapshot")?;
+        if buffer.first() != Some(&0) {
+            return Ok(StateEncoding::Json)
+        }
+
+        let mut magic = [0u8; BINARY_MAGIC.len()];
+        self.reader
+            .read_exact(&mut magic)
+            .context("failed to read the header of the binary state snapshot")?;
+        if magic != BINARY_MAGIC {
+            anyhow::bail!("unknown header of the binary state snapshot");
+        }
+        Ok(StateEncoding::Binary)
+    }
+
+    fn read_json_entry(&mut self) -> anyhow::Result<Option<StateEntry>> {
+        loop {
+            self.line.clear();
+            self.position += 1;
+            let read = self
+                .reader
+                .read_line(&mut self.line)
+                .with_context(|| format!("failed to read line {}", self.position))?;
+            if read == 0 {
+                return Ok(None)
+            }
+
+            let line = self.line.trim();
+            if line.is_empty() {
+                continue
+            }
+            let entry = serde_json::from_str(line).with_context(|| {
+                format!("invalid state entry at line {}", self.position)
+            })?;
+            return Ok(Some(entry))
+        }
+    }
+
+    fn read_binary_entry(&mut self) -> anyhow::Result<Option<StateEntry>> {
+        self.position += 1;
+        let position = self.position;
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(None)
+        }
+
+        let mut len = [0u8; 4];
+        self.reader
+            .read_exact(&mut len)
+            .with_context(|| format!("failed to read entry {position}"))?;
+        let mut bytes = vec![0u8; u32::from_le_bytes(len) as usize];
+        self.reader
+            .read_exact(&mut bytes)
+            .with_context(|| format!("failed to read entry {position}"))?;
+        let entry: BinaryEntry = postcard::from_bytes(&bytes)
+            .with_context(|| format!("invalid state entry {position}"))?;
+        Ok(Some(entry.into()))
+    }
+}
+
+impl<R: BufRead> Iterator for StateReader<R> {
+    type Item = anyhow::Result<StateEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_entry().transpose()
+    }
+}
+
+/// Writes the streamed state snapshot entry by entry.
+pub struct StateWriter<W> {
+    writer: W,
+    encoding: StateEncoding,
+}
+
+impl<W: Write> StateWriter<W> {
+    /// Creates the writer of the JSON snapshot.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            encoding: StateEncoding::Json,
+        }
+    }
+
+    /// Creates the writer of the snapshot in the `encoding`.
+    pub fn with_encoding(mut writer: W, encoding: StateEncoding) -> anyhow::Result<Self> {
+        if encoding == StateEncoding::Binary {
+            writer.write_all(BINARY_MAGIC)?;
+        }
+        Ok(Self { writer, encoding })
+    }
+
+    pub fn write(&mut self, entry: &StateEntry) -> anyhow::Result<()> {
+        match self.encoding {
+            StateEncoding::Json => {
+                serde_json::to_writer(&mut self.writer, entry)
+                    .context("failed to encode the state entry")?;
+                self.writer.write_all(b"\n")?;
+            }
+            StateEncoding::Binary => {
+                let bytes = postcard::to_stdvec(&BinaryEntry::from(entry.clone()))
+                    .context("failed to encode the state entry")?;
+                let len =
+                    u32::try_from(bytes.len()).context("the state entry is too large")?;
+                self.writer.write_all(&len.to_le_bytes())?;
+                self.writer.write_all(&bytes)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes the written entries and returns the underlying writer.
+    pub fn finish(mut self) -> anyhow::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChainConfig;
+
+    #[test]
+    fn state_roundtrips_through_the_stream() {
+        let state = ChainConfig::local_testnet()
+            .initial_state
+            .expect("The local testnet has the initial state");
+        let state = StateConfig {
+            height: Some(10u32.into()),
+            ..state
+        };
+        let entries = state.entries().collect::<Vec<_>>();
+        let balance = StateEntry::ContractBalance(ContractBalanceConfig {
+            contract_id: ContractId::from([1; 32]),
+            asset_id: AssetId::from([2; 32]),
+            amount: 3,
+        });
+
+        let mut writer = StateWriter::new(Vec::new());
+        for entry in entries.iter().chain(Some(&balance)) {
+            writer.write(entry).unwrap();
+        }
+        let bytes = writer.finish().unwrap();
+
+        let read = StateReader::new(bytes.as_slice())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(read[0], StateEntry::Height(10u32.into()));
+        assert_eq!(read.last(), Some(&balance));
+        assert_eq!(read[..read.len() - 1], entries[..]);
+    }
+
+    #[test]
+    fn binary_state_roundtrips_and_is_smaller_than_json() {
+        let state = ChainConfig::local_testnet()
+            .initial_state
+            .expect("The local testnet has the initial state");
+        let entries = state.entries().collect::<Vec<_>>();
+        let encode = |encoding| {
+            let mut writer = StateWriter::with_encoding(Vec::new(), encoding).unwrap();
+            for entry in &entries {
+                writer.write(entry).unwrap();
+            }
+            writer.finish().unwrap()
+        };
+        let binary = encode(StateEncoding::Binary);
+        let json = encode(StateEncoding::Json);
+
+        let read = StateReader::new(binary.as_slice())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(read, entries);
+        assert!(binary.len() < json.len() / 2);
+    }
+
+    #[test]
+    fn truncated_binary_entry_is_an_error() {
+        let mut writer =
+            StateWriter::with_encoding(Vec::new(), StateEncoding::Binary).unwrap();
+        writer.write(&StateEntry::Height(1u32.into())).unwrap();
+        let mut bytes = writer.finish().unwrap();
+        bytes.pop();
+
+        let mut reader = StateReader::new(bytes.as_slice());
+
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("entry 1"), "{err}");
+    }
+
+    #[test]
+    fn invalid_entry_reports_the_line() {
+        let height = StateEntry::Height(1u32.into());
+        let input = format!("{}\n\nnot json\n", serde_json::to_string(&height).unwrap());
+        let mut reader = StateReader::new(input.as_bytes());
+
+        assert_eq!(reader.next().unwrap().unwrap(), height);
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{err}");
+    }
+}
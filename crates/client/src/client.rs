@@ -14,6 +14,7 @@ use crate::client::{
         Address,
         AssetId,
         BlockId,
+        Bytes32,
         ContractId,
         UtxoId,
     },
@@ -91,6 +92,7 @@ use std::{
         ErrorKind,
     },
     net,
+    path::PathBuf,
     str::{
         self,
         FromStr,
@@ -664,6 +666,14 @@ impl FuelClient {
         Ok(new_height.into())
     }
 
+    /// Creates a consistent checkpoint of the node's database and returns its path.
+    /// The node must be started with the checkpoint directory.
+    pub async fn create_database_checkpoint(&self) -> io::Result<PathBuf> {
+        let query = schema::admin::CreateDatabaseCheckpointMutation::build(());
+        let path = self.query(query).await?.create_database_checkpoint;
+        Ok(path.into())
+    }
+
     pub async fn block(&self, id: &BlockId) -> io::Result<Option<types::Block>> {
         let query = schema::block::BlockByIdQuery::build(BlockByIdArgs {
             id: Some((*id).into()),
@@ -777,6 +787,26 @@ impl FuelClient {
         &self,
         id: &ContractId,
         asset: Option<&AssetId>,
+    ) -> io::Result<u64> {
+        self.contract_balance_query(id, asset, None).await
+    }
+
+    /// Returns the balance of the contract right after the block at the `height`.
+    /// The node must run in the archive mode.
+    pub async fn contract_balance_at_height(
+        &self,
+        id: &ContractId,
+        asset: Option<&AssetId>,
+        height: BlockHeight,
+    ) -> io::Result<u64> {
+        self.contract_balance_query(id, asset, Some(height)).await
+    }
+
+    async fn contract_balance_query(
+        &self,
+        id: &ContractId,
+        asset: Option<&AssetId>,
+        height: Option<BlockHeight>,
     ) -> io::Result<u64> {
         let asset_id: schema::AssetId = match asset {
             Some(asset) => (*asset).into(),
@@ -787,6 +817,7 @@ impl FuelClient {
             schema::contract::ContractBalanceQuery::build(ContractBalanceQueryArgs {
                 id: (*id).into(),
                 asset: asset_id,
+                block_height: height.map(Into::into),
             });
 
         let balance: types::ContractBalance =
@@ -794,17 +825,65 @@ impl FuelClient {
         Ok(balance.amount)
     }
 
+    /// Returns the value of the storage slot of the contract, or `None` if it is not set.
+    /// The `height` requires the node to run in the archive mode.
+    pub async fn contract_storage_slot(
+        &self,
+        contract: &ContractId,
+        key: &Bytes32,
+        height: Option<BlockHeight>,
+    ) -> io::Result<Option<Bytes32>> {
+        let query = schema::contract::ContractStorageSlotQuery::build(
+            schema::contract::ContractStorageSlotArgs {
+                contract: (*contract).into(),
+                key: (*key).into(),
+                block_height: height.map(Into::into),
+            },
+        );
+
+        let value = self
+            .query(query)
+            .await?
+            .contract_storage_slot
+            .map(Into::into);
+        Ok(value)
+    }
+
     pub async fn balance(
         &self,
         owner: &Address,
         asset_id: Option<&AssetId>,
+    ) -> io::Result<u64> {
+        self.balance_query(owner, asset_id, None).await
+    }
+
+    /// Returns the balance of the owner right after the block at the `height`.
+    /// The node must run in the archive mode.
+    pub async fn balance_at_height(
+        &self,
+        owner: &Address,
+        asset_id: Option<&AssetId>,
+        height: BlockHeight,
+    ) -> io::Result<u64> {
+        self.balance_query(owner, asset_id, Some(height)).await
+    }
+
+    async fn balance_query(
+        &self,
+        owner: &Address,
+        asset_id: Option<&AssetId>,
+        height: Option<BlockHeight>,
     ) -> io::Result<u64> {
         let owner: schema::Address = (*owner).into();
         let asset_id: schema::AssetId = match asset_id {
             Some(asset_id) => (*asset_id).into(),
             None => schema::AssetId::default(),
         };
-        let query = schema::balance::BalanceQuery::build(BalanceArgs { owner, asset_id });
+        let query = schema::balance::BalanceQuery::build(BalanceArgs {
+            owner,
+            asset_id,
+            block_height: height.map(Into::into),
+        });
         let balance: types::Balance = self.query(query).await?.balance.into();
         Ok(balance.amount)
     }
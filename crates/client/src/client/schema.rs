@@ -23,6 +23,7 @@ use crate::client::pagination::{
 };
 pub use primitives::*;
 
+pub mod admin;
 pub mod balance;
 pub mod block;
 pub mod chain;
@@ -0,0 +1,19 @@
+use crate::client::schema::schema;
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Mutation")]
+pub struct CreateDatabaseCheckpointMutation {
+    pub create_database_checkpoint: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_database_checkpoint_mutation_gql_output() {
+        use cynic::MutationBuilder;
+        let operation = CreateDatabaseCheckpointMutation::build(());
+        insta::assert_snapshot!(operation.query)
+    }
+}
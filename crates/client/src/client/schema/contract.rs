@@ -2,10 +2,12 @@ use crate::client::{
     schema::{
         schema,
         AssetId,
+        Bytes32,
         ContractId,
         HexString,
         PageInfo,
         Salt,
+        U32,
         U64,
     },
     PageDirection,
@@ -40,6 +42,7 @@ pub struct ContractBalance {
 pub struct ContractBalanceQueryArgs {
     pub id: ContractId,
     pub asset: AssetId,
+    pub block_height: Option<U32>,
 }
 
 #[derive(cynic::QueryFragment, Debug)]
@@ -49,10 +52,28 @@ pub struct ContractBalanceQueryArgs {
     variables = "ContractBalanceQueryArgs"
 )]
 pub struct ContractBalanceQuery {
-    #[arguments(contract: $id, asset: $asset)]
+    #[arguments(contract: $id, asset: $asset, blockHeight: $block_height)]
     pub contract_balance: ContractBalance,
 }
 
+#[derive(cynic::QueryVariables, Debug)]
+pub struct ContractStorageSlotArgs {
+    pub contract: ContractId,
+    pub key: Bytes32,
+    pub block_height: Option<U32>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    variables = "ContractStorageSlotArgs"
+)]
+pub struct ContractStorageSlotQuery {
+    #[arguments(contract: $contract, key: $key, blockHeight: $block_height)]
+    pub contract_storage_slot: Option<Bytes32>,
+}
+
 #[derive(cynic::QueryFragment, Debug)]
 #[cynic(schema_path = "./assets/schema.sdl")]
 pub struct Contract {
@@ -147,4 +168,26 @@ mod tests {
         });
         insta::assert_snapshot!(operation.query)
     }
+
+    #[test]
+    fn contract_balance_query_gql_output() {
+        use cynic::QueryBuilder;
+        let operation = ContractBalanceQuery::build(ContractBalanceQueryArgs {
+            id: ContractId::default(),
+            asset: AssetId::default(),
+            block_height: Some(U32(1)),
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+
+    #[test]
+    fn contract_storage_slot_query_gql_output() {
+        use cynic::QueryBuilder;
+        let operation = ContractStorageSlotQuery::build(ContractStorageSlotArgs {
+            contract: ContractId::default(),
+            key: Bytes32::default(),
+            block_height: Some(U32(1)),
+        });
+        insta::assert_snapshot!(operation.query)
+    }
 }
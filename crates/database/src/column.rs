@@ -0,0 +1,84 @@
+//! The columns of the database.
+
+use strum::EnumCount;
+
+/// Database tables column ids to the corresponding [`fuel_core_storage::Mappable`] table.
+#[repr(u32)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    strum_macros::EnumCount,
+    strum_macros::EnumString,
+    PartialEq,
+    Eq,
+    enum_iterator::Sequence,
+)]
+pub enum Column {
+    /// The column id of metadata about the blockchain
+    Metadata = 0,
+    /// See [`ContractsRawCode`](fuel_core_storage::tables::ContractsRawCode)
+    ContractsRawCode = 1,
+    /// See [`ContractsInfo`](fuel_core_storage::tables::ContractsInfo)
+    ContractsInfo = 2,
+    /// See [`ContractsState`](fuel_core_storage::tables::ContractsState)
+    ContractsState = 3,
+    /// See [`ContractsLatestUtxo`](fuel_core_storage::tables::ContractsLatestUtxo)
+    ContractsLatestUtxo = 4,
+    /// See [`ContractsAssets`](fuel_core_storage::tables::ContractsAssets)
+    ContractsAssets = 5,
+    /// See [`Coins`](fuel_core_storage::tables::Coins)
+    Coins = 6,
+    /// The column of the table that stores `true` if `owner` owns `Coin` with `coin_id`
+    OwnedCoins = 7,
+    /// See [`Transactions`](fuel_core_storage::tables::Transactions)
+    Transactions = 8,
+    /// Transaction id to current status
+    TransactionStatus = 9,
+    /// The column of the table of all `owner`'s transactions
+    TransactionsByOwnerBlockIdx = 10,
+    /// See [`Receipts`](fuel_core_storage::tables::Receipts)
+    Receipts = 11,
+    /// See [`FuelBlocks`](fuel_core_storage::tables::FuelBlocks)
+    FuelBlocks = 12,
+    /// See `FuelBlockIdsToHeights` of the `fuel-core` database
+    FuelBlockIdsToHeights = 13,
+    /// See [`Messages`](fuel_core_storage::tables::Messages)
+    Messages = 14,
+    /// The column of the table that stores `true` if `owner` owns `Message` with `message_id`
+    OwnedMessageIds = 15,
+    /// See [`SealedBlockConsensus`](fuel_core_storage::tables::SealedBlockConsensus)
+    FuelBlockConsensus = 16,
+    /// See `FuelBlockMerkleData` of the `fuel-core` database
+    FuelBlockMerkleData = 17,
+    /// See `FuelBlockMerkleMetadata` of the `fuel-core` database
+    FuelBlockMerkleMetadata = 18,
+    /// Messages that have been spent.
+    /// Existence of a key in this column means that the message has been spent.
+    /// See [`SpentMessages`](fuel_core_storage::tables::SpentMessages)
+    SpentMessages = 19,
+    /// Metadata for the relayer
+    /// See `RelayerMetadata` of the relayer
+    RelayerMetadata = 20,
+    /// See `ContractsAssetsMerkleData` of the `fuel-core` database
+    ContractsAssetsMerkleData = 21,
+    /// See `ContractsAssetsMerkleMetadata` of the `fuel-core` database
+    ContractsAssetsMerkleMetadata = 22,
+    /// See `ContractsStateMerkleData` of the `fuel-core` database
+    ContractsStateMerkleData = 23,
+    /// See `ContractsStateMerkleMetadata` of the `fuel-core` database
+    ContractsStateMerkleMetadata = 24,
+    /// The values of the archived columns before each block.
+    /// See the `archive` of the `fuel-core` database
+    ArchivedHistory = 25,
+}
+
+impl Column {
+    /// The total count of variants in the enum.
+    pub const COUNT: usize = <Self as EnumCount>::COUNT;
+
+    /// Returns the `usize` representation of the `Column`.
+    pub fn as_usize(&self) -> usize {
+        *self as usize
+    }
+}
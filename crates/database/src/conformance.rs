@@ -0,0 +1,420 @@
+//! The conformance test suite of the storage backends.
+//!
+//! Every check receives an empty storage and panics if the storage behaves
+//! differently from the contract of the [`KeyValueStore`] and [`BatchOperations`].
+//! The [`storage_conformance_tests`](crate::storage_conformance_tests) macro generates
+//! a test for each check:
+//!
+//! ```ignore
+//! fuel_core_database::storage_conformance_tests!(|| Arc::new(MyStorage::default()));
+//! ```
+
+use crate::{
+    kv_store::{
+        TransactableStorage,
+        Value,
+        WriteOperation,
+    },
+    Column,
+};
+use fuel_core_storage::iter::IterDirection;
+use std::sync::Arc;
+
+/// Generates a `#[test]` for each check of the [`conformance`](crate::conformance)
+/// suite in the `storage_conformance` module.
+///
+/// The argument is called to create an empty storage for every test. It returns the
+/// value referencing the storage by `as_ref`, like `Arc<Storage>`, or the wrapper
+/// that also keeps alive the resources of the storage, like a temporary directory.
+#[macro_export]
+macro_rules! storage_conformance_tests {
+    ($new_storage:expr) => {
+        $crate::storage_conformance_tests!(
+            @tests $new_storage;
+            put_returns_the_previous_value,
+            missing_key_has_no_value,
+            delete_and_take_remove_the_value,
+            write_and_replace_store_the_bytes,
+            read_copies_the_value_into_the_buffer,
+            empty_keys_and_values_are_stored,
+            columns_are_isolated,
+            iter_all_is_ordered_by_keys,
+            iter_all_filters_by_prefix,
+            iter_all_begins_from_the_start_key,
+            iter_all_combines_prefix_and_start,
+            batch_write_applies_all_operations,
+        );
+    };
+    (@tests $new_storage:expr; $($check:ident,)*) => {
+        mod storage_conformance {
+            use super::*;
+
+            $(
+                #[test]
+                fn $check() {
+                    let storage = ($new_storage)();
+                    $crate::conformance::$check(storage.as_ref());
+                }
+            )*
+        }
+    };
+}
+
+fn value(bytes: &[u8]) -> Value {
+    Arc::new(bytes.to_vec())
+}
+
+fn collect(
+    storage: &dyn TransactableStorage,
+    column: Column,
+    prefix: Option<&[u8]>,
+    start: Option<&[u8]>,
+    direction: IterDirection,
+) -> Vec<Vec<u8>> {
+    storage
+        .iter_all(column, prefix, start, direction)
+        .map(|item| item.expect("The iteration failed").0)
+        .collect()
+}
+
+fn insert_keys(storage: &dyn TransactableStorage, column: Column, keys: &[&[u8]]) {
+    for key in keys {
+        storage
+            .put(key, column, value(key))
+            .expect("Failed to put the value");
+    }
+}
+
+/// The `put` inserts the value and returns the previous one.
+pub fn put_returns_the_previous_value(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+
+    assert_eq!(storage.put(&[1, 2], column, value(&[1])).unwrap(), None);
+    assert_eq!(
+        storage.put(&[1, 2], column, value(&[2])).unwrap(),
+        Some(value(&[1]))
+    );
+
+    assert_eq!(storage.get(&[1, 2], column).unwrap(), Some(value(&[2])));
+    assert_eq!(
+        storage.read_alloc(&[1, 2], column).unwrap(),
+        Some(value(&[2]))
+    );
+    assert!(storage.exists(&[1, 2], column).unwrap());
+    assert_eq!(storage.size_of_value(&[1, 2], column).unwrap(), Some(1));
+}
+
+/// The missing key has no value, and its removal returns nothing.
+pub fn missing_key_has_no_value(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+    storage.put(&[1], column, value(&[1])).unwrap();
+
+    assert_eq!(storage.get(&[2], column).unwrap(), None);
+    assert_eq!(storage.read_alloc(&[2], column).unwrap(), None);
+    assert!(!storage.exists(&[2], column).unwrap());
+    assert_eq!(storage.size_of_value(&[2], column).unwrap(), None);
+    assert_eq!(storage.read(&[2], column, &mut [0; 4]).unwrap(), None);
+    assert_eq!(storage.delete(&[2], column).unwrap(), None);
+    assert_eq!(storage.take(&[2], column).unwrap(), None);
+    // the prefix of the existing key is another key
+    assert_eq!(storage.get(&[], column).unwrap(), None);
+    assert_eq!(storage.get(&[1, 0], column).unwrap(), None);
+}
+
+/// The `delete` and the `take` remove the value and return it.
+pub fn delete_and_take_remove_the_value(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+    storage.put(&[1], column, value(&[1])).unwrap();
+    storage.put(&[2], column, value(&[2])).unwrap();
+
+    assert_eq!(storage.delete(&[1], column).unwrap(), Some(value(&[1])));
+    assert_eq!(storage.take(&[2], column).unwrap(), Some(value(&[2])));
+
+    assert_eq!(storage.get(&[1], column).unwrap(), None);
+    assert_eq!(storage.get(&[2], column).unwrap(), None);
+    assert_eq!(storage.delete(&[1], column).unwrap(), None);
+    assert!(collect(storage, column, None, None, IterDirection::Forward).is_empty());
+}
+
+/// The `write` and the `replace` store the bytes and return their length.
+pub fn write_and_replace_store_the_bytes(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+
+    assert_eq!(storage.write(&[1], column, &[1, 2, 3]).unwrap(), 3);
+    assert_eq!(storage.get(&[1], column).unwrap(), Some(value(&[1, 2, 3])));
+
+    assert_eq!(
+        storage.replace(&[1], column, &[4, 5]).unwrap(),
+        (2, Some(value(&[1, 2, 3])))
+    );
+    assert_eq!(storage.replace(&[2], column, &[6]).unwrap(), (1, None));
+    assert_eq!(storage.get(&[1], column).unwrap(), Some(value(&[4, 5])));
+    assert_eq!(storage.get(&[2], column).unwrap(), Some(value(&[6])));
+}
+
+/// The `read` copies the value into the beginning of the buffer.
+pub fn read_copies_the_value_into_the_buffer(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+    storage.put(&[1], column, value(&[1, 2, 3])).unwrap();
+
+    let mut buf = [0; 5];
+    assert_eq!(storage.read(&[1], column, &mut buf).unwrap(), Some(3));
+    assert_eq!(buf, [1, 2, 3, 0, 0]);
+}
+
+/// The empty key and the empty value are as valid as any other.
+pub fn empty_keys_and_values_are_stored(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+    storage.put(&[], column, value(&[1])).unwrap();
+    storage.put(&[1], column, value(&[])).unwrap();
+
+    assert_eq!(storage.get(&[], column).unwrap(), Some(value(&[1])));
+    assert_eq!(storage.get(&[1], column).unwrap(), Some(value(&[])));
+    assert!(storage.exists(&[1], column).unwrap());
+    assert_eq!(storage.size_of_value(&[1], column).unwrap(), Some(0));
+    assert_eq!(
+        collect(storage, column, None, None, IterDirection::Forward),
+        vec![vec![], vec![1]]
+    );
+
+    assert_eq!(storage.delete(&[], column).unwrap(), Some(value(&[1])));
+    assert_eq!(storage.delete(&[1], column).unwrap(), Some(value(&[])));
+    assert!(!storage.exists(&[1], column).unwrap());
+}
+
+/// The same key in different columns refers to different values.
+pub fn columns_are_isolated(storage: &dyn TransactableStorage) {
+    storage.put(&[1], Column::Metadata, value(&[1])).unwrap();
+    storage.put(&[1], Column::Coins, value(&[2])).unwrap();
+
+    assert_eq!(
+        storage.get(&[1], Column::Metadata).unwrap(),
+        Some(value(&[1]))
+    );
+    assert_eq!(storage.get(&[1], Column::Coins).unwrap(), Some(value(&[2])));
+    assert_eq!(storage.get(&[1], Column::Messages).unwrap(), None);
+
+    storage.delete(&[1], Column::Coins).unwrap();
+    assert_eq!(
+        storage.get(&[1], Column::Metadata).unwrap(),
+        Some(value(&[1]))
+    );
+    assert_eq!(
+        collect(
+            storage,
+            Column::Metadata,
+            None,
+            None,
+            IterDirection::Forward
+        ),
+        vec![vec![1]]
+    );
+    assert!(
+        collect(storage, Column::Coins, None, None, IterDirection::Forward).is_empty()
+    );
+}
+
+/// The iteration returns the keys in the lexicographical order, or in the reverse.
+pub fn iter_all_is_ordered_by_keys(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+    insert_keys(storage, column, &[&[2], &[1, 0], &[1], &[0, 255], &[255]]);
+
+    let ordered = vec![vec![0, 255], vec![1], vec![1, 0], vec![2], vec![255]];
+    let items = storage
+        .iter_all(column, None, None, IterDirection::Forward)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        items,
+        ordered
+            .iter()
+            .map(|key| (key.clone(), value(key)))
+            .collect::<Vec<_>>()
+    );
+
+    let mut reversed = ordered;
+    reversed.reverse();
+    assert_eq!(
+        collect(storage, column, None, None, IterDirection::Reverse),
+        reversed
+    );
+}
+
+/// The iteration with the prefix returns only the keys starting with it.
+pub fn iter_all_filters_by_prefix(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+    insert_keys(
+        storage,
+        column,
+        &[&[0, 1], &[1], &[1, 0], &[1, 2, 3], &[2, 0]],
+    );
+
+    assert_eq!(
+        collect(storage, column, Some(&[1]), None, IterDirection::Forward),
+        vec![vec![1], vec![1, 0], vec![1, 2, 3]]
+    );
+    assert_eq!(
+        collect(storage, column, Some(&[1]), None, IterDirection::Reverse),
+        vec![vec![1, 2, 3], vec![1, 0], vec![1]]
+    );
+    assert!(
+        collect(storage, column, Some(&[3]), None, IterDirection::Forward).is_empty()
+    );
+    assert!(
+        collect(storage, column, Some(&[3]), None, IterDirection::Reverse).is_empty()
+    );
+
+    // the prefix of the length of the contract id
+    let column = Column::ContractsState;
+    let prefix = [7; 32];
+    let key = |suffix: u8| [prefix.as_slice(), &[suffix]].concat();
+    let (first, second) = (key(1), key(2));
+    let after = [8; 33];
+    insert_keys(storage, column, &[&[6; 33], &first, &second, &after]);
+    assert_eq!(
+        collect(storage, column, Some(&prefix), None, IterDirection::Forward),
+        vec![first.clone(), second.clone()]
+    );
+    assert_eq!(
+        collect(storage, column, Some(&prefix), None, IterDirection::Reverse),
+        vec![second, first]
+    );
+}
+
+/// The iteration from the start key includes it, or begins from the closest key
+/// in the direction of the iteration.
+pub fn iter_all_begins_from_the_start_key(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+    insert_keys(storage, column, &[&[1], &[3], &[5]]);
+
+    assert_eq!(
+        collect(storage, column, None, Some(&[3]), IterDirection::Forward),
+        vec![vec![3], vec![5]]
+    );
+    assert_eq!(
+        collect(storage, column, None, Some(&[3]), IterDirection::Reverse),
+        vec![vec![3], vec![1]]
+    );
+    assert_eq!(
+        collect(storage, column, None, Some(&[4]), IterDirection::Forward),
+        vec![vec![5]]
+    );
+    assert_eq!(
+        collect(storage, column, None, Some(&[4]), IterDirection::Reverse),
+        vec![vec![3], vec![1]]
+    );
+    assert!(
+        collect(storage, column, None, Some(&[6]), IterDirection::Forward).is_empty()
+    );
+    assert!(
+        collect(storage, column, None, Some(&[0]), IterDirection::Reverse).is_empty()
+    );
+}
+
+/// The iteration with both the prefix and the start key stays within the prefix.
+pub fn iter_all_combines_prefix_and_start(storage: &dyn TransactableStorage) {
+    let column = Column::Metadata;
+    insert_keys(
+        storage,
+        column,
+        &[&[0, 9], &[1, 1], &[1, 3], &[1, 5], &[2, 0]],
+    );
+
+    assert_eq!(
+        collect(
+            storage,
+            column,
+            Some(&[1]),
+            Some(&[1, 3]),
+            IterDirection::Forward
+        ),
+        vec![vec![1, 3], vec![1, 5]]
+    );
+    assert_eq!(
+        collect(
+            storage,
+            column,
+            Some(&[1]),
+            Some(&[1, 3]),
+            IterDirection::Reverse
+        ),
+        vec![vec![1, 3], vec![1, 1]]
+    );
+    assert_eq!(
+        collect(
+            storage,
+            column,
+            Some(&[1]),
+            Some(&[1, 4]),
+            IterDirection::Forward
+        ),
+        vec![vec![1, 5]]
+    );
+    assert_eq!(
+        collect(
+            storage,
+            column,
+            Some(&[1]),
+            Some(&[1, 4]),
+            IterDirection::Reverse
+        ),
+        vec![vec![1, 3], vec![1, 1]]
+    );
+    // the start key outside of the prefix
+    assert!(collect(
+        storage,
+        column,
+        Some(&[1]),
+        Some(&[0, 0]),
+        IterDirection::Forward
+    )
+    .is_empty());
+    assert!(collect(
+        storage,
+        column,
+        Some(&[1]),
+        Some(&[2, 0]),
+        IterDirection::Reverse
+    )
+    .is_empty());
+}
+
+/// The batch applies the inserts and the removals across the columns, the later
+/// operation on the same key wins.
+pub fn batch_write_applies_all_operations(storage: &dyn TransactableStorage) {
+    storage.put(&[1], Column::Coins, value(&[1])).unwrap();
+    storage.put(&[2], Column::Coins, value(&[2])).unwrap();
+
+    let operations = vec![
+        (
+            vec![0],
+            Column::Metadata,
+            WriteOperation::Insert(value(&[0])),
+        ),
+        (vec![1], Column::Coins, WriteOperation::Remove),
+        (vec![3], Column::Coins, WriteOperation::Insert(value(&[3]))),
+        (vec![4], Column::Coins, WriteOperation::Insert(value(&[4]))),
+        (vec![4], Column::Coins, WriteOperation::Remove),
+        (vec![2], Column::Coins, WriteOperation::Remove),
+        (vec![2], Column::Coins, WriteOperation::Insert(value(&[5]))),
+    ];
+    storage.batch_write(&mut operations.into_iter()).unwrap();
+
+    assert_eq!(
+        storage.get(&[0], Column::Metadata).unwrap(),
+        Some(value(&[0]))
+    );
+    assert_eq!(
+        collect(storage, Column::Coins, None, None, IterDirection::Forward),
+        vec![vec![2], vec![3]]
+    );
+    assert_eq!(storage.get(&[2], Column::Coins).unwrap(), Some(value(&[5])));
+    assert_eq!(storage.get(&[3], Column::Coins).unwrap(), Some(value(&[3])));
+
+    // the empty batch changes nothing
+    storage.batch_write(&mut std::iter::empty()).unwrap();
+    assert_eq!(
+        collect(storage, Column::Coins, None, None, IterDirection::Forward),
+        vec![vec![2], vec![3]]
+    );
+}
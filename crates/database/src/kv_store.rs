@@ -0,0 +1,132 @@
+//! The key-value storage interface of the database.
+//!
+//! The database of the node works on top of the [`DataSource`], so any storage
+//! implementing the [`TransactableStorage`] can be used as the backend of the node.
+//! The expected behaviour of the storage is checked by the `conformance` test suite,
+//! available with the `test-helpers` feature.
+
+use crate::{
+    Column,
+    Error as DatabaseError,
+    Result as DatabaseResult,
+};
+use fuel_core_storage::iter::{
+    BoxedIter,
+    IterDirection,
+};
+use std::{
+    fmt::Debug,
+    path::Path,
+    sync::Arc,
+};
+
+/// The storage backend of the database.
+pub type DataSource = Arc<dyn TransactableStorage>;
+/// The value stored in the storage.
+pub type Value = Arc<Vec<u8>>;
+/// The key-value pair returned by the iteration over the storage.
+pub type KVItem = DatabaseResult<(Vec<u8>, Value)>;
+
+/// The key-value storage split into the [`Column`]s. The keys of different columns
+/// never interfere. The empty keys and the empty values are valid.
+pub trait KeyValueStore {
+    /// Inserts the `value` by the `key`, returning the previous value.
+    fn put(
+        &self,
+        key: &[u8],
+        column: Column,
+        value: Value,
+    ) -> DatabaseResult<Option<Value>>;
+
+    /// Inserts the bytes of the `buf` by the `key`, returning the number of written
+    /// bytes.
+    fn write(&self, key: &[u8], column: Column, buf: &[u8]) -> DatabaseResult<usize>;
+
+    /// Inserts the bytes of the `buf` by the `key`, returning the number of written
+    /// bytes and the previous value.
+    fn replace(
+        &self,
+        key: &[u8],
+        column: Column,
+        buf: &[u8],
+    ) -> DatabaseResult<(usize, Option<Value>)>;
+
+    /// Removes the value by the `key`, returning it.
+    fn take(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>>;
+
+    /// Removes the value by the `key`, returning it.
+    fn delete(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>>;
+
+    /// Returns `true` if there is a value by the `key`.
+    fn exists(&self, key: &[u8], column: Column) -> DatabaseResult<bool>;
+
+    /// Returns the length of the value by the `key`, if any.
+    fn size_of_value(&self, key: &[u8], column: Column) -> DatabaseResult<Option<usize>>;
+
+    /// Returns the value by the `key`, if any.
+    fn get(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>>;
+
+    /// Copies the value by the `key` into the beginning of the `buf`, returning
+    /// the length of the value. The `buf` is expected to fit the value.
+    fn read(
+        &self,
+        key: &[u8],
+        column: Column,
+        buf: &mut [u8],
+    ) -> DatabaseResult<Option<usize>>;
+
+    /// Returns the value by the `key`, if any.
+    fn read_alloc(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>>;
+
+    /// Iterates over the key-value pairs of the `column` in the lexicographical order
+    /// of the keys, or in the reverse order.
+    ///
+    /// Only the keys starting with the `prefix` are returned, if it is set. The
+    /// iteration begins from the `start` key inclusively, or from the closest key
+    /// after it in the `direction`. The `start` outside of the `prefix` returns nothing.
+    fn iter_all(
+        &self,
+        column: Column,
+        prefix: Option<&[u8]>,
+        start: Option<&[u8]>,
+        direction: IterDirection,
+    ) -> BoxedIter<KVItem>;
+}
+
+/// The atomic writes of many values at once.
+pub trait BatchOperations: KeyValueStore {
+    /// Applies all `entries` atomically: either all of them are written, or none of them
+    /// if an error is returned. The later entry with the same key wins.
+    fn batch_write(
+        &self,
+        entries: &mut dyn Iterator<Item = (Vec<u8>, Column, WriteOperation)>,
+    ) -> DatabaseResult<()>;
+}
+
+/// The write of the [`BatchOperations::batch_write`].
+#[derive(Debug, Clone)]
+pub enum WriteOperation {
+    /// Inserts the value by the key.
+    Insert(Value),
+    /// Removes the value by the key.
+    Remove,
+}
+
+/// The storage backend of the database, see [`DataSource`].
+pub trait TransactableStorage: BatchOperations + Debug + Send + Sync {
+    /// Creates a consistent copy of the storage at the `path`.
+    fn checkpoint(&self, path: &Path) -> DatabaseResult<()> {
+        Err(DatabaseError::Other(anyhow::anyhow!(
+            "The storage doesn't support checkpoints, can't create one at {}",
+            path.display()
+        )))
+    }
+
+    /// Applies the new writes of the primary instance if the storage is a read-only
+    /// secondary instance of it.
+    fn catch_up_with_primary(&self) -> DatabaseResult<()> {
+        Err(DatabaseError::Other(anyhow::anyhow!(
+            "The storage is not a secondary instance, there is no primary to catch up with"
+        )))
+    }
+}
@@ -1,8 +1,7 @@
-//! The crate `fuel-core-storage` contains storage types, primitives, tables used by `fuel-core`.
-//! This crate doesn't contain the actual implementation of the storage. It works around the
-//! `Database` and is used by services to provide a default implementation. Primitives
-//! defined here are used by services but are flexible enough to customize the
-//! logic when the `Database` is known.
+//! The crate `fuel-core-database` contains the errors, the columns and the key-value
+//! storage interface of the databases used by `fuel-core`. The [`kv_store`] traits
+//! allow plugging any key-value storage in as the backend of the node, and the
+//! `conformance` suite of the `test-helpers` feature checks that it behaves as expected.
 
 #![deny(missing_docs)]
 #![deny(unused_crate_dependencies)]
@@ -14,6 +13,16 @@ use std::{
     io::ErrorKind,
 };
 
+mod column;
+#[cfg(feature = "test-helpers")]
+pub mod conformance;
+pub mod kv_store;
+
+pub use column::Column;
+
+/// The result of the database operations.
+pub type Result<T> = core::result::Result<T, Error>;
+
 /// The error occurred during work with any of databases.
 #[derive(thiserror::Error, Debug)]
 #[non_exhaustive]
@@ -35,6 +44,28 @@ pub enum Error {
         /// the database version expected by this build of fuel-core
         expected: u32,
     },
+    /// The database belongs to another chain.
+    #[error("The database belongs to the chain `{found}`, but `{expected}` is expected")]
+    InvalidChainName {
+        /// the chain name of the database
+        found: String,
+        /// the chain name expected by the caller
+        expected: String,
+    },
+    /// The requested historical data was removed by the pruning.
+    #[error("The requested `{entity}` was pruned, only blocks above the height {pruned_height} are available")]
+    Pruned {
+        /// the type of the requested entity
+        entity: &'static str,
+        /// the height of the last pruned block
+        pruned_height: u32,
+    },
+    /// The historical state at the requested height is not recorded by the archive.
+    #[error("The state at the height {height} is not archived")]
+    NotArchived {
+        /// the requested height
+        height: u32,
+    },
     /// Not related to database error.
     #[error(transparent)]
     Other(#[from] anyhow::Error),
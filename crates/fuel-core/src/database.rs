@@ -11,9 +11,14 @@ use fuel_core_chain_config::{
     CoinConfig,
     ContractConfig,
     MessageConfig,
+    StateEntry,
 };
 use fuel_core_storage::{
-    iter::IterDirection,
+    iter::{
+        BoxedIter,
+        IntoBoxedIter,
+        IterDirection,
+    },
     transactional::{
         StorageTransaction,
         Transactional,
@@ -32,12 +37,16 @@ use std::{
         Debug,
         Formatter,
     },
+    iter,
     marker::Send,
     ops::Deref,
     sync::Arc,
 };
 
-pub use fuel_core_database::Error;
+pub use fuel_core_database::{
+    Column,
+    Error,
+};
 pub type Result<T> = core::result::Result<T, Error>;
 
 type DatabaseError = Error;
@@ -45,10 +54,12 @@ type DatabaseResult<T> = Result<T>;
 
 // TODO: Extract `Database` and all belongs into `fuel-core-database`.
 #[cfg(feature = "rocksdb")]
-use crate::state::rocks_db::RocksDb;
+use crate::state::{
+    rocks_db::RocksDb,
+    rocks_db_config::RocksDbConfig,
+};
 #[cfg(feature = "rocksdb")]
 use std::path::Path;
-use strum::EnumCount;
 #[cfg(feature = "rocksdb")]
 use tempfile::TempDir;
 
@@ -58,6 +69,7 @@ mod block;
 mod code_root;
 mod contracts;
 mod message;
+mod pruning;
 mod receipts;
 #[cfg(feature = "relayer")]
 mod relayer;
@@ -66,84 +78,19 @@ mod state;
 
 pub(crate) mod coin;
 
+pub mod archive;
+pub mod backup;
 pub mod balances;
 pub mod metadata;
+pub mod migration;
+pub mod secondary;
+pub mod snapshot;
 pub mod storage;
 pub mod transaction;
 pub mod transactions;
+pub mod verify;
 pub mod vm_database;
 
-/// Database tables column ids to the corresponding [`fuel_core_storage::Mappable`] table.
-#[repr(u32)]
-#[derive(
-    Copy, Clone, Debug, strum_macros::EnumCount, PartialEq, Eq, enum_iterator::Sequence,
-)]
-pub enum Column {
-    /// The column id of metadata about the blockchain
-    Metadata = 0,
-    /// See [`ContractsRawCode`](fuel_core_storage::tables::ContractsRawCode)
-    ContractsRawCode = 1,
-    /// See [`ContractsInfo`](fuel_core_storage::tables::ContractsInfo)
-    ContractsInfo = 2,
-    /// See [`ContractsState`](fuel_core_storage::tables::ContractsState)
-    ContractsState = 3,
-    /// See [`ContractsLatestUtxo`](fuel_core_storage::tables::ContractsLatestUtxo)
-    ContractsLatestUtxo = 4,
-    /// See [`ContractsAssets`](fuel_core_storage::tables::ContractsAssets)
-    ContractsAssets = 5,
-    /// See [`Coins`](fuel_core_storage::tables::Coins)
-    Coins = 6,
-    /// The column of the table that stores `true` if `owner` owns `Coin` with `coin_id`
-    OwnedCoins = 7,
-    /// See [`Transactions`](fuel_core_storage::tables::Transactions)
-    Transactions = 8,
-    /// Transaction id to current status
-    TransactionStatus = 9,
-    /// The column of the table of all `owner`'s transactions
-    TransactionsByOwnerBlockIdx = 10,
-    /// See [`Receipts`](fuel_core_storage::tables::Receipts)
-    Receipts = 11,
-    /// See [`FuelBlocks`](fuel_core_storage::tables::FuelBlocks)
-    FuelBlocks = 12,
-    /// See [`FuelBlockSecondaryKeyBlockHeights`](storage::FuelBlockSecondaryKeyBlockHeights)
-    FuelBlockSecondaryKeyBlockHeights = 13,
-    /// See [`Messages`](fuel_core_storage::tables::Messages)
-    Messages = 14,
-    /// The column of the table that stores `true` if `owner` owns `Message` with `message_id`
-    OwnedMessageIds = 15,
-    /// See [`SealedBlockConsensus`](fuel_core_storage::tables::SealedBlockConsensus)
-    FuelBlockConsensus = 16,
-    /// See [`FuelBlockMerkleData`](storage::FuelBlockMerkleData)
-    FuelBlockMerkleData = 17,
-    /// See [`FuelBlockMerkleMetadata`](storage::FuelBlockMerkleMetadata)
-    FuelBlockMerkleMetadata = 18,
-    /// Messages that have been spent.
-    /// Existence of a key in this column means that the message has been spent.
-    /// See [`SpentMessages`](fuel_core_storage::tables::SpentMessages)
-    SpentMessages = 19,
-    /// Metadata for the relayer
-    /// See [`RelayerMetadata`](fuel_core_relayer::ports::RelayerMetadata)
-    RelayerMetadata = 20,
-    /// See [`ContractsAssetsMerkleData`](storage::ContractsAssetsMerkleData)
-    ContractsAssetsMerkleData = 21,
-    /// See [`ContractsAssetsMerkleMetadata`](storage::ContractsAssetsMerkleMetadata)
-    ContractsAssetsMerkleMetadata = 22,
-    /// See [`ContractsStateMerkleData`](storage::ContractsStateMerkleData)
-    ContractsStateMerkleData = 23,
-    /// See [`ContractsStateMerkleMetadata`](storage::ContractsStateMerkleMetadata)
-    ContractsStateMerkleMetadata = 24,
-}
-
-impl Column {
-    /// The total count of variants in the enum.
-    pub const COUNT: usize = <Self as EnumCount>::COUNT;
-
-    /// Returns the `usize` representation of the `Column`.
-    pub fn as_usize(&self) -> usize {
-        *self as usize
-    }
-}
-
 #[derive(Clone, Debug)]
 pub struct Database {
     data: DataSource,
@@ -193,8 +140,19 @@ impl Database {
 
     #[cfg(feature = "rocksdb")]
     pub fn open(path: &Path, capacity: impl Into<Option<usize>>) -> DatabaseResult<Self> {
+        Self::open_with_config(path, capacity, &RocksDbConfig::default())
+    }
+
+    /// Opens the database with the column families tuned by the `config`.
+    #[cfg(feature = "rocksdb")]
+    pub fn open_with_config(
+        path: &Path,
+        capacity: impl Into<Option<usize>>,
+        config: &RocksDbConfig,
+    ) -> DatabaseResult<Self> {
         use anyhow::Context;
-        let db = RocksDb::default_open(path, capacity.into()).context("Failed to open rocksdb, you may need to wipe a pre-existing incompatible db `rm -rf ~/.fuel/db`")?;
+        let columns = enum_iterator::all::<Column>().collect();
+        let db = RocksDb::open_with_config(path, columns, capacity.into(), config).context("Failed to open rocksdb. Databases of older versions are migrated at startup, use `--db-migration-dry-run` to check the pending migrations")?;
 
         Ok(Database {
             data: Arc::new(db),
@@ -466,6 +424,13 @@ impl ChainConfigDb for Database {
     fn get_block_height(&self) -> StorageResult<BlockHeight> {
         Self::latest_height(self)
     }
+
+    fn state_entries(&self) -> BoxedIter<'_, StorageResult<StateEntry>> {
+        match self.state_snapshot(None, Default::default()) {
+            Ok(entries) => entries,
+            Err(err) => iter::once(Err(err)).into_boxed(),
+        }
+    }
 }
 
 #[cfg(feature = "rocksdb")]
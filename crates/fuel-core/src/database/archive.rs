@@ -0,0 +1,364 @@
+//! The archive of the historical state.
+//!
+//! In the archive mode, the executor records the previous values of the keys of
+//! [`ARCHIVED_COLUMNS`] changed by each block into the [`Column::ArchivedHistory`].
+//! The entry is keyed by `column ++ key ++ height`, where `height` is the height of
+//! the block that changed the value. The value of the key right after the block at
+//! the height `h` is the previous value stored by the first change above `h`,
+//! or the current value if the key wasn't changed since then.
+
+use crate::database::{
+    metadata::ARCHIVE_HEIGHT_KEY,
+    transaction::DatabaseTransaction,
+    Column,
+    Database,
+    Error as DatabaseError,
+    Result as DatabaseResult,
+};
+use fuel_core_storage::{
+    iter::IterDirection,
+    ContractsAssetKey,
+    ContractsStateKey,
+    Result as StorageResult,
+};
+use fuel_core_types::{
+    entities::{
+        coins::coin::CompressedCoin,
+        message::Message,
+    },
+    fuel_asm::Word,
+    fuel_types::{
+        Address,
+        AssetId,
+        BlockHeight,
+        Bytes32,
+        ContractId,
+    },
+};
+use itertools::Itertools;
+use serde::de::DeserializeOwned;
+use std::mem::size_of;
+
+/// The columns whose history is recorded in the archive mode.
+pub(crate) const ARCHIVED_COLUMNS: [Column; 10] = [
+    Column::ContractsRawCode,
+    Column::ContractsInfo,
+    Column::ContractsLatestUtxo,
+    Column::ContractsState,
+    Column::ContractsAssets,
+    Column::Coins,
+    Column::OwnedCoins,
+    Column::Messages,
+    Column::OwnedMessageIds,
+    Column::SpentMessages,
+];
+
+fn history_prefix(column: Column, key: &[u8]) -> Vec<u8> {
+    [&(column.as_usize() as u32).to_be_bytes()[..], key].concat()
+}
+
+fn history_key(column: Column, key: &[u8], height: BlockHeight) -> Vec<u8> {
+    [history_prefix(column, key), height.to_bytes().to_vec()].concat()
+}
+
+impl DatabaseTransaction {
+    /// Records the values of the archived columns before the changes made by the block
+    /// at the `height`. Must be called before the commit of the block.
+    pub(crate) fn archive_previous_values(
+        &self,
+        height: BlockHeight,
+    ) -> DatabaseResult<()> {
+        for column in ARCHIVED_COLUMNS {
+            let previous_values = self.previous_values(column)?;
+            let history = previous_values.iter().map(|(key, value)| {
+                (
+                    history_key(column, key, height),
+                    value.as_ref().map(|value| value.as_slice()),
+                )
+            });
+            self.batch_insert(Column::ArchivedHistory, history)?;
+        }
+        Ok(())
+    }
+}
+
+impl Database {
+    /// Enables or disables the archive of the historical state.
+    ///
+    /// The state is archived starting from the latest block at the moment of enabling.
+    /// Disabling discards the archive height, so the history recorded before it is never
+    /// used to answer the queries after the archive is enabled again.
+    pub fn set_archive_mode(&self, enabled: bool) -> StorageResult<()> {
+        match (enabled, self.archive_height()?) {
+            (true, None) => {
+                let height = self.latest_height()?;
+                let _: Option<u32> =
+                    self.insert(ARCHIVE_HEIGHT_KEY, Column::Metadata, &*height)?;
+            }
+            (false, Some(_)) => {
+                let _: Option<u32> = self.remove(ARCHIVE_HEIGHT_KEY, Column::Metadata)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the balance of the `asset_id` of the contract right after the block
+    /// at the `height`.
+    pub fn contract_balance_at(
+        &self,
+        contract_id: &ContractId,
+        asset_id: &AssetId,
+        height: BlockHeight,
+    ) -> StorageResult<Option<Word>> {
+        self.ensure_archived(height)?;
+        let key = ContractsAssetKey::new(contract_id, asset_id);
+        Ok(self.value_at(Column::ContractsAssets, key.as_ref(), height)?)
+    }
+
+    /// Returns the value of the storage slot of the contract right after the block
+    /// at the `height`.
+    pub fn contract_state_at(
+        &self,
+        contract_id: &ContractId,
+        key: &Bytes32,
+        height: BlockHeight,
+    ) -> StorageResult<Option<Bytes32>> {
+        self.ensure_archived(height)?;
+        let key = ContractsStateKey::new(contract_id, key);
+        Ok(self.value_at(Column::ContractsState, key.as_ref(), height)?)
+    }
+
+    /// Returns the coins owned by the `owner` right after the block at the `height`.
+    pub fn owned_coins_at(
+        &self,
+        owner: &Address,
+        height: BlockHeight,
+    ) -> StorageResult<Vec<CompressedCoin>> {
+        self.ensure_archived(height)?;
+        let coins = self
+            .keys_at(Column::OwnedCoins, owner.as_ref(), height)?
+            .into_iter()
+            .map(|key| self.value_at(Column::Coins, &key[Address::LEN..], height))
+            .filter_map(Result::transpose)
+            .collect::<DatabaseResult<_>>()?;
+        Ok(coins)
+    }
+
+    /// Returns the messages owned by the `owner` right after the block at the `height`.
+    pub fn owned_messages_at(
+        &self,
+        owner: &Address,
+        height: BlockHeight,
+    ) -> StorageResult<Vec<Message>> {
+        self.ensure_archived(height)?;
+        let messages = self
+            .keys_at(Column::OwnedMessageIds, owner.as_ref(), height)?
+            .into_iter()
+            .map(|key| self.value_at(Column::Messages, &key[Address::LEN..], height))
+            .filter_map(Result::transpose)
+            .collect::<DatabaseResult<_>>()?;
+        Ok(messages)
+    }
+
+    pub(crate) fn ensure_archived(&self, height: BlockHeight) -> StorageResult<()> {
+        let archived = match self.archive_height()? {
+            Some(archive_height) => {
+                archive_height <= height && height <= self.latest_height()?
+            }
+            None => false,
+        };
+
+        if archived {
+            Ok(())
+        } else {
+            Err(DatabaseError::NotArchived { height: *height }.into())
+        }
+    }
+
+    pub(crate) fn raw_value_at(
+        &self,
+        column: Column,
+        key: &[u8],
+        height: BlockHeight,
+    ) -> DatabaseResult<Option<Vec<u8>>> {
+        let prefix = history_prefix(column, key);
+        let next_height = BlockHeight::from(height.saturating_add(1));
+        let start = history_key(column, key, next_height);
+        let change = self
+            .data
+            .iter_all(
+                Column::ArchivedHistory,
+                Some(&prefix),
+                Some(&start),
+                IterDirection::Forward,
+            )
+            .find(|item| {
+                !matches!(item, Ok((history_key, _)) if history_key.len() != start.len())
+            })
+            .transpose()?;
+
+        match change {
+            Some((_, previous)) => postcard::from_bytes::<Option<Vec<u8>>>(&previous)
+                .map_err(|_| DatabaseError::Codec),
+            None => Ok(self
+                .data
+                .get(key, column)?
+                .map(|value| value.as_ref().clone())),
+        }
+    }
+
+    fn value_at<V: DeserializeOwned>(
+        &self,
+        column: Column,
+        key: &[u8],
+        height: BlockHeight,
+    ) -> DatabaseResult<Option<V>> {
+        self.raw_value_at(column, key, height)?
+            .map(|value| postcard::from_bytes(&value).map_err(|_| DatabaseError::Codec))
+            .transpose()
+    }
+
+    /// Returns the keys with the `prefix` that exist right after the block at the `height`.
+    fn keys_at(
+        &self,
+        column: Column,
+        prefix: &[u8],
+        height: BlockHeight,
+    ) -> DatabaseResult<Vec<Vec<u8>>> {
+        self.iter_at(column, prefix, height)
+            .map(|item| item.map(|(key, _)| key))
+            .collect()
+    }
+
+    /// Iterates over the keys with the `prefix` and their values right after the block
+    /// at the `height`, in the order of the keys.
+    pub(crate) fn iter_at(
+        &self,
+        column: Column,
+        prefix: &[u8],
+        height: BlockHeight,
+    ) -> impl Iterator<Item = DatabaseResult<(Vec<u8>, Vec<u8>)>> + '_ {
+        let current = self
+            .data
+            .iter_all(
+                column,
+                (!prefix.is_empty()).then_some(prefix),
+                None,
+                IterDirection::Forward,
+            )
+            .map(|item| item.map(|(key, _)| key));
+
+        // The keys removed after the `height` are only present in the history.
+        let history_prefix = history_prefix(column, prefix);
+        let column_prefix_len = history_prefix.len() - prefix.len();
+        let history = self
+            .data
+            .iter_all(
+                Column::ArchivedHistory,
+                Some(&history_prefix),
+                Some(&history_prefix),
+                IterDirection::Forward,
+            )
+            .map(move |item| {
+                item.map(|(history_key, _)| {
+                    let key_end = history_key.len() - size_of::<u32>();
+                    history_key[column_prefix_len..key_end].to_vec()
+                })
+            });
+
+        // Both iterators are ordered by the key, so they are merged without
+        // loading all keys into memory.
+        current
+            .merge_by(history, |a, b| match (a, b) {
+                (Ok(a), Ok(b)) => a <= b,
+                (Err(_), _) => true,
+                (_, Err(_)) => false,
+            })
+            .dedup_by(|a, b| matches!((a, b), (Ok(a), Ok(b)) if a == b))
+            .filter_map(move |key| {
+                let key = match key {
+                    Ok(key) => key,
+                    Err(err) => return Some(Err(err)),
+                };
+                self.raw_value_at(column, &key, height)
+                    .map(|value| value.map(|value| (key, value)))
+                    .transpose()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core_storage::{
+        tables::ContractsAssets,
+        transactional::Transaction,
+        StorageAsMut,
+    };
+
+    fn commit_block(database: &Database, height: u32, balance: Option<Word>) {
+        let contract_id = ContractId::from([1; 32]);
+        let asset_id = AssetId::from([2; 32]);
+        let mut transaction = database.transaction();
+        match balance {
+            Some(balance) => {
+                transaction
+                    .as_mut()
+                    .storage::<ContractsAssets>()
+                    .insert(&ContractsAssetKey::new(&contract_id, &asset_id), &balance)
+                    .unwrap();
+            }
+            None => {
+                transaction
+                    .as_mut()
+                    .storage::<ContractsAssets>()
+                    .remove(&ContractsAssetKey::new(&contract_id, &asset_id))
+                    .unwrap();
+            }
+        }
+        transaction.archive_previous_values(height.into()).unwrap();
+        transaction.commit().unwrap();
+    }
+
+    #[test]
+    fn value_at_returns_the_value_after_the_block() {
+        let database = Database::default();
+        commit_block(&database, 1, Some(10));
+        commit_block(&database, 2, Some(20));
+        commit_block(&database, 4, None);
+        commit_block(&database, 5, Some(50));
+
+        let key = ContractsAssetKey::new(&[1; 32].into(), &[2; 32].into());
+        let value_at = |height: u32| {
+            database
+                .value_at::<Word>(Column::ContractsAssets, key.as_ref(), height.into())
+                .unwrap()
+        };
+        assert_eq!(value_at(0), None);
+        assert_eq!(value_at(1), Some(10));
+        assert_eq!(value_at(2), Some(20));
+        assert_eq!(value_at(3), Some(20));
+        assert_eq!(value_at(4), None);
+        assert_eq!(value_at(5), Some(50));
+        assert_eq!(value_at(6), Some(50));
+    }
+
+    #[test]
+    fn removed_keys_are_listed_at_the_past_height() {
+        let database = Database::default();
+        commit_block(&database, 1, Some(10));
+        commit_block(&database, 2, None);
+
+        let contract_id = ContractId::from([1; 32]);
+        let keys_at = |height: u32| {
+            database
+                .keys_at(Column::ContractsAssets, contract_id.as_ref(), height.into())
+                .unwrap()
+                .len()
+        };
+        assert_eq!(keys_at(0), 0);
+        assert_eq!(keys_at(1), 1);
+        assert_eq!(keys_at(2), 0);
+    }
+}
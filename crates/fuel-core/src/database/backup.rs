@@ -0,0 +1,153 @@
+//! The backup and restore of the database.
+//!
+//! The backup is a RocksDB checkpoint: a consistent copy of the database that can be
+//! created while the node is running. The files of the checkpoint are hard-linked when
+//! it is on the same filesystem as the database, so the backup is cheap. The restored
+//! database is a checkpoint of the backup, so the backup itself stays reusable.
+
+use crate::database::{
+    Database,
+    Error as DatabaseError,
+    Result as DatabaseResult,
+};
+use std::path::Path;
+
+#[cfg(feature = "rocksdb")]
+use crate::state::rocks_db::RocksDb;
+#[cfg(feature = "rocksdb")]
+use std::sync::Arc;
+
+impl Database {
+    /// Checks that the database belongs to the chain with the `chain_name`, and
+    /// that its version is supported by this build directly or via the migrations.
+    pub fn verify_metadata(&self, chain_name: &str) -> DatabaseResult<()> {
+        match self.get_chain_name()? {
+            Some(found) if found == chain_name => {}
+            Some(found) => {
+                return Err(DatabaseError::InvalidChainName {
+                    found,
+                    expected: chain_name.to_string(),
+                })
+            }
+            None => return Err(DatabaseError::ChainUninitialized),
+        }
+        self.pending_migrations()?;
+        Ok(())
+    }
+
+    /// Creates a consistent backup of the database at the `path`. The `path` must not
+    /// exist. The database keeps serving the reads and writes during the backup.
+    pub fn backup(&self, path: &Path) -> DatabaseResult<()> {
+        if path.exists() {
+            return Err(DatabaseError::Other(anyhow::anyhow!(
+                "The backup destination {} already exists",
+                path.display()
+            )))
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| DatabaseError::Other(e.into()))?;
+        }
+        self.data.checkpoint(path)
+    }
+
+    /// Restores the database at the `path` from the `backup` created by the
+    /// [`Database::backup`]. The backup must belong to the chain with the `chain_name`,
+    /// and the `path` must not contain any data.
+    #[cfg(feature = "rocksdb")]
+    pub fn restore(
+        backup: &Path,
+        path: &Path,
+        chain_name: &str,
+        capacity: Option<usize>,
+    ) -> DatabaseResult<()> {
+        let is_empty_dir = |path: &Path| -> std::io::Result<bool> {
+            Ok(path.read_dir()?.next().is_none())
+        };
+        if !backup.is_dir() {
+            return Err(DatabaseError::Other(anyhow::anyhow!(
+                "The backup {} doesn't exist",
+                backup.display()
+            )))
+        }
+        if path.exists() {
+            if !is_empty_dir(path).map_err(|e| DatabaseError::Other(e.into()))? {
+                return Err(DatabaseError::Other(anyhow::anyhow!(
+                    "The database {} already exists, remove it before restoring",
+                    path.display()
+                )))
+            }
+            // The checkpoint requires a non-existing directory.
+            std::fs::remove_dir(path).map_err(|e| DatabaseError::Other(e.into()))?;
+        }
+
+        let backup = Database::new(Arc::new(RocksDb::default_open(backup, capacity)?));
+        backup.verify_metadata(chain_name)?;
+        backup.data.checkpoint(path)
+    }
+}
+
+#[cfg(feature = "rocksdb")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Column;
+    use fuel_core_chain_config::ChainConfig;
+
+    fn initialized_database(path: &Path) -> Database {
+        let database = Database::open(path, None).unwrap();
+        database.init(&ChainConfig::local_testnet()).unwrap();
+        let _: Option<u32> = database.insert(b"key", Column::Metadata, &1u32).unwrap();
+        database
+    }
+
+    #[test]
+    fn restored_database_contains_the_backup_data() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let database = initialized_database(&tmp_dir.path().join("db"));
+        let backup_path = tmp_dir.path().join("backup");
+
+        database.backup(&backup_path).unwrap();
+        // The changes after the backup are not part of it.
+        let _: Option<u32> = database.insert(b"key", Column::Metadata, &2u32).unwrap();
+
+        let restored_path = tmp_dir.path().join("restored");
+        let chain_name = ChainConfig::local_testnet().chain_name;
+        Database::restore(&backup_path, &restored_path, &chain_name, None).unwrap();
+        let restored = Database::open(&restored_path, None).unwrap();
+        let value: Option<u32> = restored.get(b"key", Column::Metadata).unwrap();
+        assert_eq!(value, Some(1));
+    }
+
+    #[test]
+    fn backup_of_other_chain_is_not_restored() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let database = initialized_database(&tmp_dir.path().join("db"));
+        let backup_path = tmp_dir.path().join("backup");
+        database.backup(&backup_path).unwrap();
+
+        let restored_path = tmp_dir.path().join("restored");
+        let result = Database::restore(&backup_path, &restored_path, "other", None);
+        assert!(matches!(
+            result,
+            Err(DatabaseError::InvalidChainName { .. })
+        ));
+        assert!(!restored_path.exists());
+    }
+
+    #[test]
+    fn existing_database_is_not_overwritten() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let database = initialized_database(&tmp_dir.path().join("db"));
+        let backup_path = tmp_dir.path().join("backup");
+        database.backup(&backup_path).unwrap();
+
+        let chain_name = ChainConfig::local_testnet().chain_name;
+        let result = Database::restore(
+            &backup_path,
+            &tmp_dir.path().join("db"),
+            &chain_name,
+            None,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -1,9 +1,9 @@
 use crate::database::{
     storage::{
         DenseMerkleMetadata,
+        FuelBlockIdsToHeights,
         FuelBlockMerkleData,
         FuelBlockMerkleMetadata,
-        FuelBlockSecondaryKeyBlockHeights,
         ToDatabaseKey,
     },
     Column,
@@ -32,6 +32,7 @@ use fuel_core_types::{
             Block,
             CompressedBlock,
         },
+        consensus::Consensus,
         primitives::BlockId,
     },
     entities::message::MerkleProof,
@@ -55,12 +56,16 @@ use std::{
 impl StorageInspect<FuelBlocks> for Database {
     type Error = StorageError;
 
-    fn get(&self, key: &BlockId) -> Result<Option<Cow<CompressedBlock>>, Self::Error> {
-        Database::get(self, key.as_slice(), Column::FuelBlocks).map_err(Into::into)
+    fn get(
+        &self,
+        key: &BlockHeight,
+    ) -> Result<Option<Cow<CompressedBlock>>, Self::Error> {
+        Database::get(self, key.database_key().as_ref(), Column::FuelBlocks)
+            .map_err(Into::into)
     }
 
-    fn contains_key(&self, key: &BlockId) -> Result<bool, Self::Error> {
-        Database::contains_key(self, key.as_slice(), Column::FuelBlocks)
+    fn contains_key(&self, key: &BlockHeight) -> Result<bool, Self::Error> {
+        Database::contains_key(self, key.database_key().as_ref(), Column::FuelBlocks)
             .map_err(Into::into)
     }
 }
@@ -68,14 +73,19 @@ impl StorageInspect<FuelBlocks> for Database {
 impl StorageMutate<FuelBlocks> for Database {
     fn insert(
         &mut self,
-        key: &BlockId,
+        key: &BlockHeight,
         value: &CompressedBlock,
     ) -> Result<Option<CompressedBlock>, Self::Error> {
-        let prev = Database::insert(self, key.as_slice(), Column::FuelBlocks, value)?;
+        let prev = Database::insert(
+            self,
+            key.database_key().as_ref(),
+            Column::FuelBlocks,
+            value,
+        )?;
 
-        let height = value.header().height();
-        self.storage::<FuelBlockSecondaryKeyBlockHeights>()
-            .insert(height, key)?;
+        let block_id = value.id();
+        self.storage::<FuelBlockIdsToHeights>()
+            .insert(&block_id, key)?;
 
         // Get latest metadata entry
         let prev_metadata = self
@@ -92,7 +102,7 @@ impl StorageMutate<FuelBlocks> for Database {
         let mut tree: MerkleTree<FuelBlockMerkleData, _> =
             MerkleTree::load(storage, prev_metadata.version)
                 .map_err(|err| StorageError::Other(err.into()))?;
-        let data = key.as_slice();
+        let data = block_id.as_slice();
         tree.push(data)?;
 
         // Generate new metadata for the updated tree
@@ -100,24 +110,24 @@ impl StorageMutate<FuelBlocks> for Database {
         let root = tree.root();
         let metadata = DenseMerkleMetadata { version, root };
         self.storage::<FuelBlockMerkleMetadata>()
-            .insert(height, &metadata)?;
+            .insert(key, &metadata)?;
 
         Ok(prev)
     }
 
-    fn remove(&mut self, key: &BlockId) -> Result<Option<CompressedBlock>, Self::Error> {
+    fn remove(
+        &mut self,
+        key: &BlockHeight,
+    ) -> Result<Option<CompressedBlock>, Self::Error> {
         let prev: Option<CompressedBlock> =
-            Database::remove(self, key.as_slice(), Column::FuelBlocks)?;
+            Database::remove(self, key.database_key().as_ref(), Column::FuelBlocks)?;
 
         if let Some(block) = &prev {
-            let height = block.header().height();
-            let _ = self
-                .storage::<FuelBlockSecondaryKeyBlockHeights>()
-                .remove(height);
+            let _ = self.storage::<FuelBlockIdsToHeights>().remove(&block.id());
             // We can't clean up `MerkleTree<FuelBlockMerkleData>`.
             // But if we plan to insert a new block, it will override old values in the
             // `FuelBlockMerkleData` table.
-            let _ = self.storage::<FuelBlockMerkleMetadata>().remove(height);
+            let _ = self.storage::<FuelBlockMerkleMetadata>().remove(key);
         }
 
         Ok(prev)
@@ -133,79 +143,82 @@ impl Database {
 
     /// Get the current block at the head of the chain.
     pub fn get_current_block(&self) -> StorageResult<Option<Cow<CompressedBlock>>> {
-        let block_ids = self.ids_of_latest_block()?;
-        match block_ids {
-            Some((_, id)) => Ok(StorageAsRef::storage::<FuelBlocks>(self).get(&id)?),
-            None => Ok(None),
-        }
+        let block = self
+            .all_blocks(None, IterDirection::Reverse)
+            .next()
+            .transpose()?
+            .map(|(_, block)| Cow::Owned(block));
+        Ok(block)
     }
 
     pub fn block_time(&self, height: &BlockHeight) -> StorageResult<Tai64> {
-        let id = self.get_block_id(height)?.unwrap_or_default();
         let block = self
             .storage::<FuelBlocks>()
-            .get(&id)?
+            .get(height)?
             .ok_or(not_found!(FuelBlocks))?;
         Ok(block.header().time().to_owned())
     }
 
     pub fn get_block_id(&self, height: &BlockHeight) -> StorageResult<Option<BlockId>> {
-        Database::get(
-            self,
-            height.database_key().as_ref(),
-            Column::FuelBlockSecondaryKeyBlockHeights,
-        )
-        .map_err(Into::into)
+        Ok(self
+            .storage::<FuelBlocks>()
+            .get(height)?
+            .map(|block| block.id()))
+    }
+
+    pub fn get_block_height(&self, id: &BlockId) -> StorageResult<Option<BlockHeight>> {
+        Ok(self
+            .storage::<FuelBlockIdsToHeights>()
+            .get(id)?
+            .map(Cow::into_owned))
     }
 
-    pub fn all_block_ids(
+    /// Iterates over the blocks in the order of their heights, starting from the `start`.
+    pub fn all_blocks(
         &self,
         start: Option<BlockHeight>,
         direction: IterDirection,
-    ) -> impl Iterator<Item = DatabaseResult<(BlockHeight, BlockId)>> + '_ {
+    ) -> impl Iterator<Item = DatabaseResult<(BlockHeight, CompressedBlock)>> + '_ {
         let start = start.map(|b| b.to_bytes());
-        self.iter_all_by_start::<Vec<u8>, BlockId, _>(
-            Column::FuelBlockSecondaryKeyBlockHeights,
+        self.iter_all_by_start::<Vec<u8>, CompressedBlock, _>(
+            Column::FuelBlocks,
             start,
             Some(direction),
         )
         .map(|res| {
-            let (height, id) = res?;
+            let (height, block) = res?;
             let block_height_bytes: [u8; 4] = height
                 .as_slice()
                 .try_into()
                 .expect("block height always has correct number of bytes");
-            Ok((block_height_bytes.into(), id))
+            Ok((block_height_bytes.into(), block))
         })
     }
 
-    pub fn ids_of_genesis_block(&self) -> DatabaseResult<(BlockHeight, BlockId)> {
-        self.iter_all(
-            Column::FuelBlockSecondaryKeyBlockHeights,
+    /// Returns the height of the genesis block.
+    ///
+    /// The consensus of the block is never pruned, so the height is known
+    /// even when the genesis block itself is pruned.
+    pub fn genesis_height(&self) -> DatabaseResult<BlockHeight> {
+        self.iter_all::<Vec<u8>, Consensus>(
+            Column::FuelBlockConsensus,
             Some(IterDirection::Forward),
         )
         .next()
         .ok_or(DatabaseError::ChainUninitialized)?
-        .map(|(height, id): (Vec<u8>, BlockId)| {
+        .map(|(height, _)| {
             let bytes = <[u8; 4]>::try_from(height.as_slice())
                 .expect("all block heights are stored with the correct amount of bytes");
-            (u32::from_be_bytes(bytes).into(), id)
+            u32::from_be_bytes(bytes).into()
         })
     }
 
     pub fn ids_of_latest_block(&self) -> DatabaseResult<Option<(BlockHeight, BlockId)>> {
         let ids = self
-            .iter_all::<Vec<u8>, BlockId>(
-                Column::FuelBlockSecondaryKeyBlockHeights,
-                Some(IterDirection::Reverse),
-            )
+            .all_blocks(None, IterDirection::Reverse)
             .next()
             .transpose()?
-            .map(|(height, block)| {
-                // safety: we know that all block heights are stored with the correct amount of bytes
-                let bytes = <[u8; 4]>::try_from(height.as_slice()).unwrap();
-                (u32::from_be_bytes(bytes).into(), block)
-            });
+            .map(|(height, block)| (height, block.id()));
 
         Ok(ids)
     }
@@ -213,9 +226,13 @@ impl Database {
     /// Retrieve the full block and all associated transactions
     pub(crate) fn get_full_block(
         &self,
-        block_id: &BlockId,
+        height: &BlockHeight,
     ) -> StorageResult<Option<Block>> {
-        let db_block = self.storage::<FuelBlocks>().get(block_id)?;
+        if self.is_pruned(height)? {
+            return Ok(None)
+        }
+
+        let db_block = self.storage::<FuelBlocks>().get(height)?;
         if let Some(block) = db_block {
             // fetch all the transactions
             // TODO: optimize with multi-key get
@@ -236,6 +253,37 @@ impl Database {
     }
 }
 
+/// Migrates the database from the blocks and their consensus keyed by the block id
+/// to the blocks and their consensus keyed by the height.
+///
+/// The height to id index is replaced with the id to height index. The index is
+/// rewritten first, because the consensus of the pruned blocks is assigned to
+/// the height through it.
+pub(crate) fn key_blocks_by_height(database: &Database) -> DatabaseResult<()> {
+    database.rewrite_column(Column::FuelBlockIdsToHeights, |height, id| {
+        let height: [u8; 4] = height.try_into().map_err(|_| DatabaseError::Codec)?;
+        let height = BlockHeight::from(height);
+        let id: BlockId = postcard::from_bytes(id).map_err(|_| DatabaseError::Codec)?;
+        let height = postcard::to_stdvec(&height).map_err(|_| DatabaseError::Codec)?;
+        Ok(Some((id.as_slice().to_vec(), height)))
+    })?;
+
+    database.rewrite_column(Column::FuelBlockConsensus, |id, consensus| {
+        let height = database.get::<BlockHeight>(id, Column::FuelBlockIdsToHeights)?;
+        // The consensus of the unknown block can't be assigned to any height.
+        Ok(height.map(|height| (height.to_bytes().to_vec(), consensus.to_vec())))
+    })?;
+
+    database.rewrite_column(Column::FuelBlocks, |_, block| {
+        let decoded: CompressedBlock =
+            postcard::from_bytes(block).map_err(|_| DatabaseError::Codec)?;
+        let height = decoded.header().height().to_bytes().to_vec();
+        Ok(Some((height, block.to_vec())))
+    })?;
+
+    Ok(())
+}
+
 impl MerkleRootStorage<BlockHeight, FuelBlocks> for Database {
     fn root(
         &self,
@@ -291,6 +339,7 @@ impl Database {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fuel_core_storage::tables::SealedBlockConsensus;
     use fuel_core_types::{
         blockchain::{
             block::PartialFuelBlock,
@@ -334,7 +383,7 @@ mod tests {
         for block in &blocks {
             StorageMutate::<FuelBlocks>::insert(
                 &mut database,
-                &block.id(),
+                block.header().height(),
                 &block.compress(&ConsensusParameters::DEFAULT.chain_id),
             )
             .unwrap();
@@ -398,7 +447,7 @@ mod tests {
         for block in &blocks {
             StorageMutate::<FuelBlocks>::insert(
                 database,
-                &block.id(),
+                block.header().height(),
                 &block.compress(&ConsensusParameters::DEFAULT.chain_id),
             )
             .unwrap();
@@ -453,4 +502,63 @@ mod tests {
         );
         assert!(result.is_err());
     }
+
+    #[test]
+    fn blocks_keyed_by_id_are_migrated_to_height_keys() {
+        let database = Database::default();
+        let blocks = (0..3u32)
+            .map(|height| {
+                let header = PartialBlockHeader {
+                    application: Default::default(),
+                    consensus: ConsensusHeader::<Empty> {
+                        height: height.into(),
+                        ..Default::default()
+                    },
+                };
+                PartialFuelBlock::new(header, vec![])
+                    .generate(&[])
+                    .compress(&ConsensusParameters::DEFAULT.chain_id)
+            })
+            .collect::<Vec<_>>();
+
+        // The layout of the database before the migration.
+        for block in &blocks {
+            let id = block.id();
+            let _: Option<CompressedBlock> = database
+                .insert(id.as_slice(), Column::FuelBlocks, block)
+                .unwrap();
+            let _: Option<Consensus> = database
+                .insert(
+                    id.as_slice(),
+                    Column::FuelBlockConsensus,
+                    &Consensus::default(),
+                )
+                .unwrap();
+            let _: Option<BlockId> = database
+                .insert(
+                    block.header().height().to_bytes(),
+                    Column::FuelBlockIdsToHeights,
+                    &id,
+                )
+                .unwrap();
+        }
+        // The pruned block is known only by the index and the consensus.
+        let _: Option<CompressedBlock> = database
+            .remove(blocks[0].id().as_slice(), Column::FuelBlocks)
+            .unwrap();
+
+        key_blocks_by_height(&database).unwrap();
+
+        for (height, block) in (0..3u32).map(BlockHeight::from).zip(&blocks) {
+            let id = block.id();
+            assert_eq!(database.get_block_height(&id).unwrap(), Some(height));
+            assert!(database
+                .storage::<SealedBlockConsensus>()
+                .contains_key(&height)
+                .unwrap());
+            let expected_id = (*height != 0).then_some(id);
+            assert_eq!(database.get_block_id(&height).unwrap(), expected_id);
+        }
+        assert_eq!(database.genesis_height().unwrap(), BlockHeight::from(0u32));
+    }
 }
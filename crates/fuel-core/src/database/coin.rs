@@ -44,6 +44,26 @@ fn utxo_id_to_bytes(utxo_id: &UtxoId) -> [u8; TxId::LEN + 1] {
     default
 }
 
+/// Returns the config of the `coin` stored under the `key` in the [`Column::Coins`].
+pub(crate) fn coin_config(
+    key: &[u8],
+    coin: CompressedCoin,
+) -> DatabaseResult<CoinConfig> {
+    let tx_id = Bytes32::new(key[..32].try_into().map_err(DatabaseError::from)?);
+    let output_index = key[32];
+
+    Ok(CoinConfig {
+        tx_id: Some(tx_id),
+        output_index: Some(output_index),
+        tx_pointer_block_height: Some(coin.tx_pointer.block_height()),
+        tx_pointer_tx_idx: Some(coin.tx_pointer.tx_index()),
+        maturity: Some(coin.maturity),
+        owner: coin.owner,
+        amount: coin.amount,
+        asset_id: coin.asset_id,
+    })
+}
+
 /// The storage table of owned coin ids. Maps addresses to owned coins.
 pub struct OwnedCoins;
 /// The storage key for owned coins: `Address ++ UtxoId`
@@ -138,27 +158,18 @@ impl Database {
         Ok(coin)
     }
 
-    pub fn get_coin_config(&self) -> DatabaseResult<Option<Vec<CoinConfig>>> {
-        let configs = self
-            .iter_all::<Vec<u8>, CompressedCoin>(Column::Coins, None)
+    /// Returns the configs of all unspent coins one by one.
+    pub fn coin_configs(&self) -> impl Iterator<Item = DatabaseResult<CoinConfig>> + '_ {
+        self.iter_all::<Vec<u8>, CompressedCoin>(Column::Coins, None)
             .map(|raw_coin| -> DatabaseResult<CoinConfig> {
-                let coin = raw_coin?;
-
-                let byte_id =
-                    Bytes32::new(coin.0[..32].try_into().map_err(DatabaseError::from)?);
-                let output_index = coin.0[32];
-
-                Ok(CoinConfig {
-                    tx_id: Some(byte_id),
-                    output_index: Some(output_index),
-                    tx_pointer_block_height: Some(coin.1.tx_pointer.block_height()),
-                    tx_pointer_tx_idx: Some(coin.1.tx_pointer.tx_index()),
-                    maturity: Some(coin.1.maturity),
-                    owner: coin.1.owner,
-                    amount: coin.1.amount,
-                    asset_id: coin.1.asset_id,
-                })
+                let (key, coin) = raw_coin?;
+                coin_config(&key, coin)
             })
+    }
+
+    pub fn get_coin_config(&self) -> DatabaseResult<Option<Vec<CoinConfig>>> {
+        let configs = self
+            .coin_configs()
             .collect::<DatabaseResult<Vec<CoinConfig>>>()?;
 
         Ok(Some(configs))
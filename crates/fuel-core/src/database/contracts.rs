@@ -8,6 +8,7 @@ use crate::database::{
 use fuel_core_chain_config::ContractConfig;
 use fuel_core_storage::{
     iter::IterDirection,
+    not_found,
     tables::{
         ContractsInfo,
         ContractsLatestUtxo,
@@ -31,6 +32,7 @@ use fuel_core_types::{
         AssetId,
         Bytes32,
         ContractId,
+        Salt,
         Word,
     },
 };
@@ -159,23 +161,7 @@ impl Database {
             .unwrap()
             .into_owned()
             .into();
-
-        let (salt, _) = self
-            .storage::<ContractsInfo>()
-            .get(&contract_id)
-            .unwrap()
-            .expect("Contract does not exist")
-            .into_owned();
-
-        let ContractUtxoInfo {
-            utxo_id,
-            tx_pointer,
-        } = self
-            .storage::<ContractsLatestUtxo>()
-            .get(&contract_id)
-            .unwrap()
-            .expect("contract does not exist")
-            .into_owned();
+        let mut config = self.contract_config_without_storage(contract_id, code)?;
 
         let state = Some(
             self.iter_all_by_prefix::<Vec<u8>, Bytes32, _>(
@@ -213,17 +199,30 @@ impl Database {
             .collect::<StorageResult<Vec<(AssetId, u64)>>>()?,
         );
 
-        Ok(ContractConfig {
-            contract_id,
-            code,
-            salt,
-            state,
-            balances,
-            tx_id: Some(*utxo_id.tx_id()),
-            output_index: Some(utxo_id.output_index()),
-            tx_pointer_block_height: Some(tx_pointer.block_height()),
-            tx_pointer_tx_idx: Some(tx_pointer.tx_index()),
-        })
+        config.state = state;
+        config.balances = balances;
+        Ok(config)
+    }
+
+    /// Returns the config of the contract without its state and balances.
+    fn contract_config_without_storage(
+        &self,
+        contract_id: ContractId,
+        code: Vec<u8>,
+    ) -> StorageResult<ContractConfig> {
+        let (salt, _) = self
+            .storage::<ContractsInfo>()
+            .get(&contract_id)?
+            .ok_or(not_found!(ContractsInfo))?
+            .into_owned();
+
+        let utxo_info = self
+            .storage::<ContractsLatestUtxo>()
+            .get(&contract_id)?
+            .ok_or(not_found!(ContractsLatestUtxo))?
+            .into_owned();
+
+        Ok(contract_config(contract_id, code, salt, utxo_info))
     }
 
     pub fn contract_balances(
@@ -262,6 +261,31 @@ impl Database {
     }
 }
 
+/// Returns the config of the contract without its state and balances.
+pub(crate) fn contract_config(
+    contract_id: ContractId,
+    code: Vec<u8>,
+    salt: Salt,
+    utxo_info: ContractUtxoInfo,
+) -> ContractConfig {
+    let ContractUtxoInfo {
+        utxo_id,
+        tx_pointer,
+    } = utxo_info;
+
+    ContractConfig {
+        contract_id,
+        code,
+        salt,
+        state: None,
+        balances: None,
+        tx_id: Some(*utxo_id.tx_id()),
+        output_index: Some(utxo_id.output_index()),
+        tx_pointer_block_height: Some(tx_pointer.block_height()),
+        tx_pointer_tx_idx: Some(tx_pointer.tx_index()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,9 +118,11 @@ impl Database {
             .map(|res| res.map(|(_, message)| message))
     }
 
-    pub fn get_message_config(&self) -> StorageResult<Option<Vec<MessageConfig>>> {
-        let configs = self
-            .all_messages(None, None)
+    /// Returns the configs of all unspent messages one by one.
+    pub fn message_configs(
+        &self,
+    ) -> impl Iterator<Item = StorageResult<MessageConfig>> + '_ {
+        self.all_messages(None, None)
             .filter_map(|msg| {
                 // Return only unspent messages
                 if let Ok(msg) = msg {
@@ -133,18 +135,12 @@ impl Database {
                     Some(msg.map_err(StorageError::from))
                 }
             })
-            .map(|msg| -> StorageResult<MessageConfig> {
-                let msg = msg?;
-
-                Ok(MessageConfig {
-                    sender: msg.sender,
-                    recipient: msg.recipient,
-                    nonce: msg.nonce,
-                    amount: msg.amount,
-                    data: msg.data,
-                    da_height: msg.da_height,
-                })
-            })
+            .map(|msg| msg.map(MessageConfig::from))
+    }
+
+    pub fn get_message_config(&self) -> StorageResult<Option<Vec<MessageConfig>>> {
+        let configs = self
+            .message_configs()
             .collect::<StorageResult<Vec<MessageConfig>>>()?;
 
         Ok(Some(configs))
@@ -1,20 +1,40 @@
 use crate::database::{
+    migration::{
+        run_migrations,
+        Migration,
+        MIGRATIONS,
+    },
     Column,
     Database,
     Error as DatabaseError,
     Result as DatabaseResult,
 };
 use fuel_core_chain_config::ChainConfig;
+use fuel_core_types::fuel_types::BlockHeight;
 
 pub(crate) const DB_VERSION_KEY: &[u8] = b"version";
 pub(crate) const CHAIN_NAME_KEY: &[u8] = b"chain_name";
+pub(crate) const PRUNED_HEIGHT_KEY: &[u8] = b"pruned_height";
+pub(crate) const ARCHIVE_HEIGHT_KEY: &[u8] = b"archive_height";
+pub(crate) const GENESIS_PROGRESS_KEY: &[u8] = b"genesis_progress";
 
-/// Can be used to perform migrations in the future.
-pub(crate) const DB_VERSION: u32 = 0x00;
+/// The version of the database format. Each increment requires a corresponding
+/// migration in the [`MIGRATIONS`](crate::database::migration::MIGRATIONS).
+pub(crate) const DB_VERSION: u32 = 0x01;
 
 impl Database {
-    /// Ensures the database is initialized and that the database version is correct
+    /// Ensures the database is initialized and that the database version is correct.
+    /// Applies all pending migrations if the database version is outdated.
     pub fn init(&self, config: &ChainConfig) -> DatabaseResult<()> {
+        self.init_with_migrations(config, MIGRATIONS, DB_VERSION)
+    }
+
+    pub(crate) fn init_with_migrations(
+        &self,
+        config: &ChainConfig,
+        migrations: &[Migration],
+        version: u32,
+    ) -> DatabaseResult<()> {
         // initialize chain name if not set
         if self.get_chain_name()?.is_none() {
             self.insert(CHAIN_NAME_KEY, Column::Metadata, &config.chain_name)
@@ -27,17 +47,19 @@ impl Database {
                 })?;
         }
 
+        run_migrations(self, migrations, version)?;
+
         // Ensure the database version is correct
-        if let Some(version) = self.get::<u32>(DB_VERSION_KEY, Column::Metadata)? {
-            if version != DB_VERSION {
+        if let Some(found) = self.get::<u32>(DB_VERSION_KEY, Column::Metadata)? {
+            if found != version {
                 return Err(DatabaseError::InvalidDatabaseVersion {
-                    found: version,
-                    expected: DB_VERSION,
+                    found,
+                    expected: version,
                 })?
             }
         } else {
             let _: Option<u32> =
-                self.insert(DB_VERSION_KEY, Column::Metadata, &DB_VERSION)?;
+                self.insert(DB_VERSION_KEY, Column::Metadata, &version)?;
         }
         Ok(())
     }
@@ -45,4 +67,44 @@ impl Database {
     pub fn get_chain_name(&self) -> DatabaseResult<Option<String>> {
         self.get(CHAIN_NAME_KEY, Column::Metadata)
     }
+
+    /// Returns the height of the last block pruned by the [`Database::prune_blocks`].
+    pub fn pruned_height(&self) -> DatabaseResult<Option<BlockHeight>> {
+        Ok(self
+            .get::<u32>(PRUNED_HEIGHT_KEY, Column::Metadata)?
+            .map(Into::into))
+    }
+
+    /// Returns `true` if the transactions of the block at the `height` were pruned.
+    pub fn is_pruned(&self, height: &BlockHeight) -> DatabaseResult<bool> {
+        Ok(
+            matches!(self.pruned_height()?, Some(pruned_height) if *height <= pruned_height),
+        )
+    }
+
+    /// Returns the height starting from which the state is archived,
+    /// see [`Database::set_archive_mode`].
+    pub fn archive_height(&self) -> DatabaseResult<Option<BlockHeight>> {
+        Ok(self
+            .get::<u32>(ARCHIVE_HEIGHT_KEY, Column::Metadata)?
+            .map(Into::into))
+    }
+
+    /// Returns the number of the state entries imported by the unfinished genesis.
+    pub fn genesis_progress(&self) -> DatabaseResult<Option<u64>> {
+        self.get(GENESIS_PROGRESS_KEY, Column::Metadata)
+    }
+
+    /// Records the number of the state entries imported by the genesis so far.
+    pub fn set_genesis_progress(&mut self, imported: u64) -> DatabaseResult<()> {
+        let _: Option<u64> =
+            self.insert(GENESIS_PROGRESS_KEY, Column::Metadata, &imported)?;
+        Ok(())
+    }
+
+    /// Removes the progress of the genesis after the genesis block is committed.
+    pub fn clear_genesis_progress(&mut self) -> DatabaseResult<()> {
+        let _: Option<u64> = self.remove(GENESIS_PROGRESS_KEY, Column::Metadata)?;
+        Ok(())
+    }
 }
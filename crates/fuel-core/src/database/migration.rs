@@ -0,0 +1,687 @@
+//! The registry of the database migrations.
+//!
+//! Each time the encoding of a [`Column`] changes, the [`DB_VERSION`] must be increased
+//! and a [`Migration`] that rewrites the old data into the new format must be appended
+//! to the [`MIGRATIONS`]. At startup, the node applies all pending migrations in order,
+//! so the upgrade doesn't require a resync or a regenesis.
+//!
+//! Migrations are applied directly to the database in batches of [`BATCH_SIZE`] entries.
+//! The progress of the running migration is stored in the [`Column::Metadata`], so an
+//! interrupted migration continues from the last committed batch after the restart.
+//! The version of the database is bumped only after the migration is finished.
+
+use crate::{
+    database::{
+        block,
+        metadata::{
+            DB_VERSION,
+            DB_VERSION_KEY,
+        },
+        Column,
+        Database,
+        Error as DatabaseError,
+        Result as DatabaseResult,
+    },
+    state::WriteOperation,
+};
+use fuel_core_storage::iter::IterDirection;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::{
+    collections::HashSet,
+    iter,
+    sync::Arc,
+};
+
+#[cfg(feature = "rocksdb")]
+use crate::state::{
+    rocks_db::RocksDb,
+    TransactableStorage,
+};
+#[cfg(feature = "rocksdb")]
+use std::path::Path;
+
+/// The number of entries rewritten and committed at once.
+const BATCH_SIZE: usize = 100_000;
+
+/// The prefix of the keys in the [`Column::Metadata`] that store the progress
+/// of the running migration per column.
+const PROGRESS_KEY_PREFIX: &[u8] = b"migration_progress";
+
+/// The prefix of the keys in the [`Column::Metadata`] that store the entries moved
+/// to a new key by the running migration, until they are written back to the column.
+const STAGING_KEY_PREFIX: &[u8] = b"migration_staging";
+
+/// The ordered list of all migrations. The version of the last migration is
+/// always equal to the [`DB_VERSION`].
+pub(crate) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "Key the blocks and their consensus by the block height",
+    migrate: block::key_blocks_by_height,
+}];
+
+/// The migration of the database from the `version - 1` to the `version`.
+#[derive(Clone, Copy)]
+pub struct Migration {
+    /// The version of the database after the migration.
+    pub version: u32,
+    /// The human-readable description of the migration used in the logs.
+    pub description: &'static str,
+    /// Rewrites the data of the database into the format of the `version`.
+    ///
+    /// The function is called again after the restart if the migration was interrupted,
+    /// so it must be safe to re-run. Each column should be rewritten at most once per
+    /// migration with the [`Database::rewrite_column`], which continues from the last
+    /// committed batch.
+    pub migrate: fn(&Database) -> DatabaseResult<()>,
+}
+
+/// The progress of the [`Database::rewrite_column`] within the running migration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum RewriteProgress {
+    /// The entries up to the `last_key` inclusively are rewritten.
+    Rewriting { last_key: Vec<u8> },
+    /// All entries are rewritten, and the staged entries are moving back to the column.
+    Moving,
+    /// The column is rewritten.
+    Done,
+}
+
+impl Database {
+    /// Migrates the database to the [`DB_VERSION`].
+    ///
+    /// Returns the versions of the executed migrations. If the database is not
+    /// initialized yet, there is nothing to migrate.
+    pub fn migrate(&self) -> DatabaseResult<Vec<u32>> {
+        run_migrations(self, MIGRATIONS, DB_VERSION)
+    }
+
+    /// Returns the migrations that [`Database::migrate`] would apply.
+    pub fn pending_migrations(&self) -> DatabaseResult<Vec<&'static Migration>> {
+        pending_migrations(self, MIGRATIONS, DB_VERSION)
+    }
+
+    /// Applies the pending migrations to a checkpoint of the database at the `path`
+    /// and removes the checkpoint afterward. The database itself stays untouched.
+    ///
+    /// The checkpoint is created next to the database, so its files are hard-linked,
+    /// and only the rewritten data takes additional space.
+    #[cfg(feature = "rocksdb")]
+    pub fn dry_run_migrations(
+        path: &Path,
+        capacity: Option<usize>,
+    ) -> DatabaseResult<Vec<u32>> {
+        dry_run_migrations(path, capacity, MIGRATIONS, DB_VERSION)
+    }
+
+    /// Rewrites each entry of the `column` with the result of the `f`.
+    ///
+    /// The `f` receives the raw key and value and returns the new key and value of
+    /// the entry. If the `f` returns `None`, the entry is removed. Returns the number
+    /// of entries processed by this call.
+    ///
+    /// The rewriting has two phases, and each batch is committed with the progress.
+    /// At first, the entries are rewritten in place, and the entries with a changed key
+    /// are removed from the column and staged. After that, the staged entries are written
+    /// back under their new keys. It guarantees that a moved entry never overwrites
+    /// an entry that is not rewritten yet. If two entries end up with the same key,
+    /// the error is returned.
+    pub fn rewrite_column<F>(&self, column: Column, mut f: F) -> DatabaseResult<usize>
+    where
+        F: FnMut(&[u8], &[u8]) -> DatabaseResult<Option<(Vec<u8>, Vec<u8>)>>,
+    {
+        let progress_key = column_key(PROGRESS_KEY_PREFIX, column);
+        let staging_prefix = column_key(STAGING_KEY_PREFIX, column);
+        let mut progress =
+            self.get::<RewriteProgress>(&progress_key, Column::Metadata)?;
+        let mut processed = 0;
+
+        loop {
+            let last_key = match &progress {
+                None => None,
+                Some(RewriteProgress::Rewriting { last_key }) => {
+                    Some(last_key.as_slice())
+                }
+                Some(RewriteProgress::Moving | RewriteProgress::Done) => break,
+            };
+
+            // The start of the iteration is inclusive, so the last rewritten entry is skipped.
+            let batch = self
+                .data
+                .iter_all(column, None, last_key, IterDirection::Forward)
+                .skip_while(|item| {
+                    matches!(item, Ok((key, _)) if Some(key.as_slice()) == last_key)
+                })
+                .take(BATCH_SIZE)
+                .collect::<DatabaseResult<Vec<_>>>()?;
+
+            let next_progress = match batch.last() {
+                Some((key, _)) => RewriteProgress::Rewriting {
+                    last_key: key.clone(),
+                },
+                None => RewriteProgress::Moving,
+            };
+
+            let mut staged = HashSet::new();
+            let mut operations = Vec::with_capacity(batch.len() + 1);
+            for (key, value) in batch.iter() {
+                match f(key, value.as_slice())? {
+                    Some((new_key, new_value)) if new_key == *key => {
+                        operations.push((
+                            new_key,
+                            column,
+                            WriteOperation::Insert(Arc::new(new_value)),
+                        ));
+                    }
+                    Some((new_key, new_value)) => {
+                        let staging_key = [staging_prefix.as_slice(), &new_key].concat();
+                        if !staged.insert(staging_key.clone())
+                            || self.data.exists(&staging_key, Column::Metadata)?
+                        {
+                            return Err(key_collision(column, &new_key))
+                        }
+                        operations.push((key.clone(), column, WriteOperation::Remove));
+                        operations.push((
+                            staging_key,
+                            Column::Metadata,
+                            WriteOperation::Insert(Arc::new(new_value)),
+                        ));
+                    }
+                    None => {
+                        operations.push((key.clone(), column, WriteOperation::Remove));
+                    }
+                }
+            }
+            operations.push((
+                progress_key.clone(),
+                Column::Metadata,
+                WriteOperation::Insert(encode(&next_progress)?),
+            ));
+            self.data.batch_write(&mut operations.into_iter())?;
+
+            if !batch.is_empty() {
+                processed += batch.len();
+                tracing::info!(
+                    "Rewritten {} entries of the {:?} column",
+                    processed,
+                    column
+                );
+            }
+            progress = Some(next_progress);
+        }
+
+        if progress == Some(RewriteProgress::Moving) {
+            loop {
+                // The processed entries are removed from the staging area,
+                // so each batch starts from the beginning of it.
+                let batch = self
+                    .data
+                    .iter_all(
+                        Column::Metadata,
+                        Some(&staging_prefix),
+                        Some(&staging_prefix),
+                        IterDirection::Forward,
+                    )
+                    .take(BATCH_SIZE)
+                    .collect::<DatabaseResult<Vec<_>>>()?;
+
+                if batch.is_empty() {
+                    break
+                }
+
+                let mut operations = Vec::with_capacity(batch.len() * 2);
+                for (staging_key, value) in batch {
+                    let key = staging_key[staging_prefix.len()..].to_vec();
+                    if self.data.exists(&key, column)? {
+                        return Err(key_collision(column, &key))
+                    }
+                    operations.push((key, column, WriteOperation::Insert(value)));
+                    operations.push((
+                        staging_key,
+                        Column::Metadata,
+                        WriteOperation::Remove,
+                    ));
+                }
+                self.data.batch_write(&mut operations.into_iter())?;
+            }
+
+            self.data.put(
+                &progress_key,
+                Column::Metadata,
+                encode(&RewriteProgress::Done)?,
+            )?;
+        }
+
+        Ok(processed)
+    }
+}
+
+/// Returns the migrations required to upgrade the `database` to the `target_version`.
+fn pending_migrations<'a>(
+    database: &Database,
+    migrations: &'a [Migration],
+    target_version: u32,
+) -> DatabaseResult<Vec<&'a Migration>> {
+    let current_version = match database.get::<u32>(DB_VERSION_KEY, Column::Metadata)? {
+        Some(version) => version,
+        None => return Ok(vec![]),
+    };
+
+    if current_version > target_version {
+        return Err(DatabaseError::InvalidDatabaseVersion {
+            found: current_version,
+            expected: target_version,
+        })
+    }
+
+    let pending: Vec<_> = migrations
+        .iter()
+        .filter(|migration| {
+            migration.version > current_version && migration.version <= target_version
+        })
+        .collect();
+
+    let has_full_path = pending
+        .iter()
+        .map(|migration| migration.version)
+        .eq(current_version + 1..=target_version);
+    if !has_full_path {
+        return Err(DatabaseError::InvalidDatabaseVersion {
+            found: current_version,
+            expected: target_version,
+        })
+    }
+
+    Ok(pending)
+}
+
+pub(crate) fn run_migrations(
+    database: &Database,
+    migrations: &[Migration],
+    target_version: u32,
+) -> DatabaseResult<Vec<u32>> {
+    let pending = pending_migrations(database, migrations, target_version)?;
+    if pending.is_empty() {
+        return Ok(vec![])
+    }
+
+    tracing::info!("Migrating the database to version {:#x}", target_version);
+
+    let mut applied = Vec::with_capacity(pending.len());
+    for migration in pending {
+        tracing::info!(
+            "Applying migration {:#x}: {}",
+            migration.version,
+            migration.description
+        );
+        (migration.migrate)(database)?;
+
+        // The progress of the finished migration is removed atomically with the version bump.
+        let progress = database
+            .data
+            .iter_all(
+                Column::Metadata,
+                Some(PROGRESS_KEY_PREFIX),
+                Some(PROGRESS_KEY_PREFIX),
+                IterDirection::Forward,
+            )
+            .map(|item| {
+                item.map(|(key, _)| (key, Column::Metadata, WriteOperation::Remove))
+            })
+            .collect::<DatabaseResult<Vec<_>>>()?;
+        let version = (
+            DB_VERSION_KEY.to_vec(),
+            Column::Metadata,
+            WriteOperation::Insert(encode(&migration.version)?),
+        );
+        database
+            .data
+            .batch_write(&mut progress.into_iter().chain(iter::once(version)))?;
+        applied.push(migration.version);
+    }
+
+    tracing::info!("The database is migrated to version {:#x}", target_version);
+    Ok(applied)
+}
+
+#[cfg(feature = "rocksdb")]
+fn dry_run_migrations(
+    path: &Path,
+    capacity: Option<usize>,
+    migrations: &[Migration],
+    target_version: u32,
+) -> DatabaseResult<Vec<u32>> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let checkpoint_dir = tempfile::Builder::new()
+        .prefix("migration-dry-run")
+        .tempdir_in(parent)
+        .map_err(|e| DatabaseError::Other(e.into()))?;
+    let checkpoint_path = checkpoint_dir.path().join("db");
+
+    RocksDb::default_open(path, capacity)?.checkpoint(&checkpoint_path)?;
+    let checkpoint = Database::open(&checkpoint_path, capacity)?;
+    run_migrations(&checkpoint, migrations, target_version)
+}
+
+fn column_key(prefix: &[u8], column: Column) -> Vec<u8> {
+    [prefix, &(column.as_usize() as u32).to_be_bytes()].concat()
+}
+
+fn encode<V: Serialize>(value: &V) -> DatabaseResult<Arc<Vec<u8>>> {
+    postcard::to_stdvec(value)
+        .map(Arc::new)
+        .map_err(|_| DatabaseError::Codec)
+}
+
+fn key_collision(column: Column, key: &[u8]) -> DatabaseError {
+    DatabaseError::Other(anyhow::anyhow!(
+        "The migration writes several entries with the key {:?} into the {:?} column",
+        key,
+        column
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core_chain_config::ChainConfig;
+
+    fn database_at_version(version: u32) -> Database {
+        let database = Database::in_memory();
+        let _: Option<u32> = database
+            .insert(DB_VERSION_KEY, Column::Metadata, &version)
+            .unwrap();
+        database
+    }
+
+    fn version_of(database: &Database) -> u32 {
+        database
+            .get(DB_VERSION_KEY, Column::Metadata)
+            .unwrap()
+            .unwrap()
+    }
+
+    fn double_values(database: &Database) -> DatabaseResult<()> {
+        database
+            .rewrite_column(Column::Receipts, |key, value| {
+                let value: u64 =
+                    postcard::from_bytes(value).map_err(|_| DatabaseError::Codec)?;
+                let value = postcard::to_stdvec(&(value * 2))
+                    .map_err(|_| DatabaseError::Codec)?;
+                Ok(Some((key.to_vec(), value)))
+            })
+            .map(|_| ())
+    }
+
+    fn prefix_keys(database: &Database) -> DatabaseResult<()> {
+        database
+            .rewrite_column(Column::Receipts, |key, value| {
+                let mut new_key = vec![0xFF];
+                new_key.extend_from_slice(key);
+                Ok(Some((new_key, value.to_vec())))
+            })
+            .map(|_| ())
+    }
+
+    fn strip_prefix(database: &Database) -> DatabaseResult<()> {
+        database
+            .rewrite_column(Column::Receipts, |key, value| {
+                let new_key = key.strip_prefix(&[0xFF]).unwrap_or(key);
+                Ok(Some((new_key.to_vec(), value.to_vec())))
+            })
+            .map(|_| ())
+    }
+
+    const TEST_MIGRATIONS: &[Migration] = &[
+        Migration {
+            version: 1,
+            description: "double values",
+            migrate: double_values,
+        },
+        Migration {
+            version: 2,
+            description: "prefix keys",
+            migrate: prefix_keys,
+        },
+    ];
+
+    #[test]
+    fn registry_is_consistent_with_db_version() {
+        let latest = MIGRATIONS.last().map(|m| m.version).unwrap_or(0);
+        assert_eq!(latest, DB_VERSION);
+        for (expected, migration) in (1..).zip(MIGRATIONS) {
+            assert_eq!(migration.version, expected);
+        }
+    }
+
+    #[test]
+    fn uninitialized_database_is_not_migrated() {
+        let database = Database::in_memory();
+        let applied = run_migrations(&database, TEST_MIGRATIONS, 2).unwrap();
+        assert!(applied.is_empty());
+    }
+
+    #[test]
+    fn migrations_are_applied_in_order() {
+        let database = database_at_version(0);
+        let _: Option<u64> = database.insert([1u8], Column::Receipts, &21u64).unwrap();
+
+        let applied = run_migrations(&database, TEST_MIGRATIONS, 2).unwrap();
+
+        assert_eq!(applied, vec![1, 2]);
+        assert_eq!(version_of(&database), 2);
+        assert_eq!(
+            database.get::<u64>(&[0xFF, 1], Column::Receipts).unwrap(),
+            Some(42)
+        );
+        assert_eq!(database.get::<u64>(&[1], Column::Receipts).unwrap(), None);
+    }
+
+    #[test]
+    fn only_pending_migrations_are_applied() {
+        let database = database_at_version(1);
+        let _: Option<u64> = database.insert([1u8], Column::Receipts, &21u64).unwrap();
+
+        let applied = run_migrations(&database, TEST_MIGRATIONS, 2).unwrap();
+
+        assert_eq!(applied, vec![2]);
+        assert_eq!(
+            database.get::<u64>(&[0xFF, 1], Column::Receipts).unwrap(),
+            Some(21)
+        );
+    }
+
+    #[test]
+    fn pending_migrations_are_listed_without_changes() {
+        let database = database_at_version(1);
+        let _: Option<u64> = database.insert([1u8], Column::Receipts, &21u64).unwrap();
+
+        let pending = pending_migrations(&database, TEST_MIGRATIONS, 2).unwrap();
+
+        assert_eq!(
+            pending.iter().map(|m| m.version).collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(version_of(&database), 1);
+        assert_eq!(
+            database.get::<u64>(&[1], Column::Receipts).unwrap(),
+            Some(21)
+        );
+    }
+
+    #[test]
+    fn overlapping_keys_are_preserved() {
+        let database = database_at_version(1);
+        let _: Option<u64> = database.insert([1u8], Column::Receipts, &1u64).unwrap();
+        let _: Option<u64> = database
+            .insert([0xFFu8, 1], Column::Receipts, &2u64)
+            .unwrap();
+
+        run_migrations(&database, TEST_MIGRATIONS, 2).unwrap();
+
+        assert_eq!(database.get::<u64>(&[1], Column::Receipts).unwrap(), None);
+        assert_eq!(
+            database.get::<u64>(&[0xFF, 1], Column::Receipts).unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            database
+                .get::<u64>(&[0xFF, 0xFF, 1], Column::Receipts)
+                .unwrap(),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn colliding_keys_are_rejected() {
+        let database = database_at_version(0);
+        let _: Option<u64> = database.insert([1u8], Column::Receipts, &1u64).unwrap();
+        let _: Option<u64> = database
+            .insert([0xFFu8, 1], Column::Receipts, &2u64)
+            .unwrap();
+        let migrations = [Migration {
+            version: 1,
+            description: "strip prefix",
+            migrate: strip_prefix,
+        }];
+
+        let result = run_migrations(&database, &migrations, 1);
+
+        assert!(matches!(result, Err(DatabaseError::Other(_))));
+        assert_eq!(version_of(&database), 0);
+        assert_eq!(
+            database.get::<u64>(&[1], Column::Receipts).unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn interrupted_rewrite_is_resumed() {
+        let database = database_at_version(0);
+        // The first entry was already rewritten before the interruption.
+        let _: Option<u64> = database.insert([1u8], Column::Receipts, &42u64).unwrap();
+        let _: Option<u64> = database.insert([2u8], Column::Receipts, &21u64).unwrap();
+        let _: Option<RewriteProgress> = database
+            .insert(
+                column_key(PROGRESS_KEY_PREFIX, Column::Receipts),
+                Column::Metadata,
+                &RewriteProgress::Rewriting { last_key: vec![1] },
+            )
+            .unwrap();
+
+        run_migrations(&database, &TEST_MIGRATIONS[..1], 1).unwrap();
+
+        assert_eq!(version_of(&database), 1);
+        assert_eq!(
+            database.get::<u64>(&[1], Column::Receipts).unwrap(),
+            Some(42)
+        );
+        assert_eq!(
+            database.get::<u64>(&[2], Column::Receipts).unwrap(),
+            Some(42)
+        );
+        assert_eq!(
+            database
+                .get::<RewriteProgress>(
+                    &column_key(PROGRESS_KEY_PREFIX, Column::Receipts),
+                    Column::Metadata
+                )
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn newer_database_is_rejected() {
+        let database = database_at_version(3);
+        let result = run_migrations(&database, TEST_MIGRATIONS, 2);
+        assert!(matches!(
+            result,
+            Err(DatabaseError::InvalidDatabaseVersion {
+                found: 3,
+                expected: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn missing_migration_is_rejected() {
+        let database = database_at_version(0);
+        let result = run_migrations(&database, &TEST_MIGRATIONS[1..], 2);
+        assert!(matches!(
+            result,
+            Err(DatabaseError::InvalidDatabaseVersion {
+                found: 0,
+                expected: 2
+            })
+        ));
+        assert_eq!(version_of(&database), 0);
+    }
+
+    #[test]
+    fn init_applies_migrations_before_version_check() {
+        let database = database_at_version(0);
+        let _: Option<u64> = database.insert([1u8], Column::Receipts, &21u64).unwrap();
+
+        database
+            .init_with_migrations(&ChainConfig::local_testnet(), TEST_MIGRATIONS, 2)
+            .unwrap();
+
+        assert_eq!(version_of(&database), 2);
+        assert_eq!(
+            database.get::<u64>(&[0xFF, 1], Column::Receipts).unwrap(),
+            Some(42)
+        );
+    }
+
+    #[test]
+    fn init_fails_if_migration_is_missing() {
+        let database = database_at_version(0);
+
+        let result = database.init_with_migrations(
+            &ChainConfig::local_testnet(),
+            &TEST_MIGRATIONS[..1],
+            2,
+        );
+
+        assert!(matches!(
+            result,
+            Err(DatabaseError::InvalidDatabaseVersion {
+                found: 0,
+                expected: 2
+            })
+        ));
+    }
+
+    #[cfg(feature = "rocksdb")]
+    #[test]
+    fn dry_run_keeps_database_untouched() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let path = tmp_dir.path().join("db");
+        {
+            let database = Database::open(&path, None).unwrap();
+            let _: Option<u32> = database
+                .insert(DB_VERSION_KEY, Column::Metadata, &0u32)
+                .unwrap();
+            let _: Option<u64> =
+                database.insert([1u8], Column::Receipts, &21u64).unwrap();
+        }
+
+        let applied = dry_run_migrations(&path, None, TEST_MIGRATIONS, 2).unwrap();
+
+        assert_eq!(applied, vec![1, 2]);
+        let database = Database::open(&path, None).unwrap();
+        assert_eq!(version_of(&database), 0);
+        assert_eq!(
+            database.get::<u64>(&[1], Column::Receipts).unwrap(),
+            Some(21)
+        );
+        // The checkpoint is removed after the dry run.
+        assert_eq!(std::fs::read_dir(tmp_dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::database::{
+    metadata::PRUNED_HEIGHT_KEY,
+    transactions::{
+        transaction_owners,
+        TransactionIndex,
+    },
+    Column,
+    Database,
+};
+use fuel_core_storage::{
+    tables::{
+        FuelBlocks,
+        Receipts,
+        Transactions,
+    },
+    transactional::Transaction as StorageTransactionTrait,
+    Result as StorageResult,
+    StorageAsMut,
+};
+use fuel_core_types::{
+    fuel_types::BlockHeight,
+    services::txpool::TransactionStatus,
+};
+use std::borrow::Cow;
+
+impl Database {
+    /// Prunes the historical data of the blocks up to the `height` inclusively.
+    ///
+    /// The blocks are pruned in the order of their heights, starting from the block
+    /// after the [`Database::pruned_height`], and not more than `limit` blocks per call.
+    /// It allows the caller to spread the pruning of the long history over time.
+    /// Returns the number of pruned blocks.
+    pub fn prune_blocks(&self, height: BlockHeight, limit: u32) -> StorageResult<u32> {
+        let next_height = match self.pruned_height()? {
+            Some(pruned_height) => *pruned_height + 1,
+            None => *self.genesis_height()?,
+        };
+
+        let mut pruned = 0;
+        for next_height in (next_height..=*height).take(limit as usize) {
+            self.prune_block(next_height.into())?;
+            pruned += 1;
+        }
+        Ok(pruned)
+    }
+
+    /// Removes the transactions of the block at the `height`, their receipts, statuses
+    /// and the owners index entries atomically.
+    ///
+    /// The compressed block, its consensus and the merkle data are kept, so the chain is
+    /// still verifiable, and the block can be identified as pruned.
+    fn prune_block(&self, height: BlockHeight) -> StorageResult<()> {
+        let mut transaction = self.transaction();
+        let db = transaction.as_mut();
+
+        let block = db
+            .storage::<FuelBlocks>()
+            .get(&height)?
+            .map(Cow::into_owned);
+
+        if let Some(block) = block {
+            for (tx_idx, tx_id) in block.transactions().iter().enumerate() {
+                if let Some(tx) = db.storage::<Transactions>().remove(tx_id)? {
+                    for owner in transaction_owners(&tx) {
+                        db.remove_tx_id_owner(
+                            &owner,
+                            height,
+                            tx_idx as TransactionIndex,
+                        )?;
+                    }
+                }
+                db.storage::<Receipts>().remove(tx_id)?;
+                let _: Option<TransactionStatus> =
+                    db.remove(tx_id.as_ref(), Column::TransactionStatus)?;
+            }
+        }
+
+        let _: Option<u32> = db.insert(PRUNED_HEIGHT_KEY, Column::Metadata, &*height)?;
+        transaction.commit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core_storage::tables::SealedBlockConsensus;
+    use fuel_core_types::{
+        blockchain::{
+            block::PartialFuelBlock,
+            header::{
+                ConsensusHeader,
+                PartialBlockHeader,
+            },
+            primitives::Empty,
+        },
+        fuel_tx::{
+            Address,
+            ConsensusParameters,
+            Output,
+            Transaction,
+            TxPointer,
+            UniqueIdentifier,
+        },
+    };
+
+    fn owner(height: u32) -> Address {
+        Address::from([height as u8; 32])
+    }
+
+    fn insert_block(
+        database: &mut Database,
+        height: u32,
+    ) -> fuel_core_types::fuel_tx::TxId {
+        let tx: Transaction = Transaction::mint(
+            TxPointer::new(height.into(), 0),
+            vec![Output::coin(owner(height), 1, Default::default())],
+        )
+        .into();
+        let tx_id = tx.id(&ConsensusParameters::DEFAULT.chain_id);
+        let header = PartialBlockHeader {
+            application: Default::default(),
+            consensus: ConsensusHeader::<Empty> {
+                height: height.into(),
+                ..Default::default()
+            },
+        };
+        let block = PartialFuelBlock::new(header, vec![tx.clone()]).generate(&[]);
+
+        database
+            .storage::<FuelBlocks>()
+            .insert(
+                &height.into(),
+                &block.compress(&ConsensusParameters::DEFAULT.chain_id),
+            )
+            .unwrap();
+        database
+            .storage::<SealedBlockConsensus>()
+            .insert(&height.into(), &Default::default())
+            .unwrap();
+        database
+            .storage::<Transactions>()
+            .insert(&tx_id, &tx)
+            .unwrap();
+        database
+            .record_tx_id_owner(&owner(height), height.into(), 0, &tx_id)
+            .unwrap();
+        tx_id
+    }
+
+    #[test]
+    fn blocks_are_pruned_in_batches_up_to_height() {
+        let mut database = Database::default();
+        let tx_ids: Vec<_> = (0..6)
+            .map(|height| insert_block(&mut database, height))
+            .collect();
+
+        assert_eq!(database.prune_blocks(3u32.into(), 2).unwrap(), 2);
+        assert_eq!(database.pruned_height().unwrap(), Some(1u32.into()));
+        assert_eq!(database.prune_blocks(3u32.into(), 2).unwrap(), 2);
+        assert_eq!(database.pruned_height().unwrap(), Some(3u32.into()));
+        assert_eq!(database.prune_blocks(3u32.into(), 2).unwrap(), 0);
+
+        for height in 0..6u32 {
+            let tx_id = &tx_ids[height as usize];
+            let pruned = height <= 3;
+
+            assert!(database.get_block_id(&height.into()).unwrap().is_some());
+            assert_eq!(
+                database.get_full_block(&height.into()).unwrap().is_some(),
+                !pruned
+            );
+            assert!(database
+                .storage::<SealedBlockConsensus>()
+                .contains_key(&height.into())
+                .unwrap());
+            assert_eq!(
+                database
+                    .storage::<Transactions>()
+                    .contains_key(tx_id)
+                    .unwrap(),
+                !pruned
+            );
+            assert_eq!(
+                database
+                    .owned_transactions(owner(height), None, None)
+                    .count(),
+                usize::from(!pruned)
+            );
+        }
+    }
+}
@@ -38,10 +38,22 @@ impl Database {
         &self,
         block_id: &BlockId,
     ) -> StorageResult<Option<SealedBlock>> {
-        // combine the block and consensus metadata into a sealed fuel block type
+        let height = match self.get_block_height(block_id)? {
+            Some(height) => height,
+            None => return Ok(None),
+        };
+        self.get_sealed_block_by_height(&height)
+    }
 
-        let block = self.get_full_block(block_id)?;
-        let consensus = self.storage::<SealedBlockConsensus>().get(block_id)?;
+    /// Returns `SealedBlock` by `height`.
+    /// Reusable across different trait implementations
+    pub fn get_sealed_block_by_height(
+        &self,
+        height: &BlockHeight,
+    ) -> StorageResult<Option<SealedBlock>> {
+        // combine the block and consensus metadata into a sealed fuel block type
+        let block = self.get_full_block(height)?;
+        let consensus = self.storage::<SealedBlockConsensus>().get(height)?;
 
         if let (Some(block), Some(consensus)) = (block, consensus) {
             let sealed_block = SealedBlock {
@@ -55,24 +67,11 @@ impl Database {
         }
     }
 
-    /// Returns `SealedBlock` by `height`.
-    /// Reusable across different trait implementations
-    pub fn get_sealed_block_by_height(
-        &self,
-        height: &BlockHeight,
-    ) -> StorageResult<Option<SealedBlock>> {
-        let block_id = match self.get_block_id(height)? {
-            Some(i) => i,
-            None => return Ok(None),
-        };
-        self.get_sealed_block_by_id(&block_id)
-    }
-
     pub fn get_genesis(&self) -> StorageResult<Genesis> {
-        let (_, genesis_block_id) = self.ids_of_genesis_block()?;
+        let genesis_height = self.genesis_height()?;
         let consensus = self
             .storage::<SealedBlockConsensus>()
-            .get(&genesis_block_id)?
+            .get(&genesis_height)?
             .map(|c| c.into_owned());
 
         if let Some(Consensus::Genesis(genesis)) = consensus {
@@ -86,19 +85,8 @@ impl Database {
         &self,
         height: &BlockHeight,
     ) -> StorageResult<Option<SealedBlockHeader>> {
-        let block_id = match self.get_block_id(height)? {
-            Some(i) => i,
-            None => return Ok(None),
-        };
-        self.get_sealed_block_header(&block_id)
-    }
-
-    pub fn get_sealed_block_header(
-        &self,
-        block_id: &BlockId,
-    ) -> StorageResult<Option<SealedBlockHeader>> {
-        let header = self.storage::<FuelBlocks>().get(block_id)?;
-        let consensus = self.storage::<SealedBlockConsensus>().get(block_id)?;
+        let header = self.storage::<FuelBlocks>().get(height)?;
+        let consensus = self.storage::<SealedBlockConsensus>().get(height)?;
 
         if let (Some(header), Some(consensus)) = (header, consensus) {
             let sealed_block = SealedBlockHeader {
@@ -112,6 +100,17 @@ impl Database {
         }
     }
 
+    pub fn get_sealed_block_header(
+        &self,
+        block_id: &BlockId,
+    ) -> StorageResult<Option<SealedBlockHeader>> {
+        let height = match self.get_block_height(block_id)? {
+            Some(height) => height,
+            None => return Ok(None),
+        };
+        self.get_sealed_block_header_by_height(&height)
+    }
+
     pub fn get_transactions_on_block(
         &self,
         block_id: &BlockId,
@@ -0,0 +1,121 @@
+//! The read-only secondary instance of the database.
+//!
+//! The secondary instance reads the RocksDB directory written by another process, the
+//! primary instance, and sees its writes only after catching up with it. It allows
+//! running several query-only nodes on top of the database of one block-importing node.
+
+use crate::database::{
+    Database,
+    Error as DatabaseError,
+    Result as DatabaseResult,
+};
+
+#[cfg(feature = "rocksdb")]
+use crate::{
+    database::Column,
+    state::{
+        rocks_db::RocksDb,
+        rocks_db_config::RocksDbConfig,
+    },
+};
+#[cfg(feature = "rocksdb")]
+use std::{
+    path::Path,
+    sync::Arc,
+};
+
+impl Database {
+    /// Opens the read-only secondary instance of the database at the `path`. The info
+    /// logs of the secondary instance are kept in a temporary directory removed on drop.
+    #[cfg(feature = "rocksdb")]
+    pub fn open_secondary(
+        path: &Path,
+        capacity: impl Into<Option<usize>>,
+        config: &RocksDbConfig,
+    ) -> DatabaseResult<Self> {
+        use anyhow::Context;
+        let secondary_dir =
+            tempfile::TempDir::new().map_err(|e| DatabaseError::Other(e.into()))?;
+        let columns = enum_iterator::all::<Column>().collect();
+        let db = RocksDb::open_secondary(
+            path,
+            secondary_dir.path(),
+            columns,
+            capacity.into(),
+            config,
+        )
+        .with_context(|| {
+            format!(
+                "Failed to open the secondary instance of the database at {}",
+                path.display()
+            )
+        })?;
+
+        Ok(Database {
+            data: Arc::new(db),
+            _drop: Arc::new(
+                {
+                    move || {
+                        // cleanup the logs of the secondary instance
+                        drop(secondary_dir);
+                    }
+                }
+                .into(),
+            ),
+        })
+    }
+
+    /// Applies the writes made by the primary instance since the last catch-up.
+    pub fn catch_up_with_primary(&self) -> DatabaseResult<()> {
+        self.data.catch_up_with_primary()
+    }
+
+    /// Checks that the secondary instance can serve the chain with the `chain_name`.
+    /// Unlike the primary instance, it can't initialize or migrate the database.
+    pub fn verify_secondary(&self, chain_name: &str) -> DatabaseResult<()> {
+        self.verify_metadata(chain_name)?;
+        if !self.pending_migrations()?.is_empty() {
+            return Err(DatabaseError::Other(anyhow::anyhow!(
+                "The database has pending migrations, start the primary instance to apply them"
+            )))
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rocksdb")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core_chain_config::ChainConfig;
+
+    #[test]
+    fn secondary_follows_the_primary() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let primary = Database::open(tmp_dir.path(), None).unwrap();
+        let config = ChainConfig::local_testnet();
+        primary.init(&config).unwrap();
+
+        let secondary =
+            Database::open_secondary(tmp_dir.path(), None, &RocksDbConfig::default())
+                .unwrap();
+        secondary.verify_secondary(&config.chain_name).unwrap();
+
+        let _: Option<u32> = primary.insert(b"key", Column::Metadata, &1u32).unwrap();
+        secondary.catch_up_with_primary().unwrap();
+        let value: Option<u32> = secondary.get(b"key", Column::Metadata).unwrap();
+        assert_eq!(value, Some(1));
+    }
+
+    #[test]
+    fn uninitialized_database_is_not_served() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let _primary = Database::open(tmp_dir.path(), None).unwrap();
+
+        let secondary =
+            Database::open_secondary(tmp_dir.path(), None, &RocksDbConfig::default())
+                .unwrap();
+        let result = secondary.verify_secondary(&ChainConfig::local_testnet().chain_name);
+        assert!(matches!(result, Err(DatabaseError::ChainUninitialized)));
+    }
+}
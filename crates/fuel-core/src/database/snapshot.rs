@@ -0,0 +1,403 @@
+//! The snapshot of the state at the latest or a past block height.
+//!
+//! The snapshot is produced entry by entry in the streamed format of the
+//! [`StateEntry`], optionally limited to the part of the state selected by the
+//! [`SnapshotFilter`].
+
+use crate::database::{
+    coin::coin_config,
+    contracts::contract_config,
+    Column,
+    Database,
+    Error as DatabaseError,
+    Result as DatabaseResult,
+};
+use fuel_core_chain_config::{
+    ContractBalanceConfig,
+    ContractStateConfig,
+    LastBlockConfig,
+    MessageConfig,
+    StateEntry,
+};
+use fuel_core_storage::{
+    iter::{
+        BoxedIter,
+        IntoBoxedIter,
+        IterDirection,
+    },
+    not_found,
+    tables::{
+        ContractsInfo,
+        ContractsLatestUtxo,
+        ContractsRawCode,
+        FuelBlocks,
+    },
+    Result as StorageResult,
+    StorageAsRef,
+};
+use fuel_core_types::{
+    entities::{
+        coins::coin::CompressedCoin,
+        contract::ContractUtxoInfo,
+        message::Message,
+    },
+    fuel_asm::Word,
+    fuel_types::{
+        Address,
+        AssetId,
+        BlockHeight,
+        Bytes32,
+        ContractId,
+        Salt,
+    },
+};
+use serde::de::DeserializeOwned;
+use std::{
+    iter,
+    rc::Rc,
+};
+
+/// Selects the part of the state included into the snapshot.
+/// The empty list doesn't restrict the corresponding entries.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SnapshotFilter {
+    /// Only the coins and the messages owned by these addresses.
+    pub owners: Vec<Address>,
+    /// Only the coins and the contract balances of these assets.
+    pub asset_ids: Vec<AssetId>,
+    /// Only these contracts.
+    pub contract_ids: Vec<ContractId>,
+}
+
+impl SnapshotFilter {
+    fn includes_asset(&self, asset_id: &AssetId) -> bool {
+        self.asset_ids.is_empty() || self.asset_ids.contains(asset_id)
+    }
+}
+
+impl Database {
+    /// Returns the entries of the state selected by the `filter` right after the block
+    /// at the `height`, or the latest state if the `height` is `None`.
+    ///
+    /// The state at the past height requires the archive of the historical state,
+    /// see [`Database::set_archive_mode`].
+    pub fn state_snapshot(
+        &self,
+        height: Option<BlockHeight>,
+        filter: SnapshotFilter,
+    ) -> StorageResult<BoxedIter<'_, StorageResult<StateEntry>>> {
+        if let Some(height) = height {
+            self.ensure_archived(height)?;
+        }
+        let view = StateView {
+            database: self,
+            height,
+        };
+        let filter = Rc::new(filter);
+
+        let height = iter::once_with(move || match height {
+            Some(height) => Ok(StateEntry::Height(height)),
+            None => self.latest_height().map(StateEntry::Height),
+        });
+
+        Ok(height
+            .chain(view.coins(filter.clone()))
+            .chain(view.contracts(filter.clone()))
+            .chain(view.messages(filter))
+            .into_boxed())
+    }
+
+    /// Returns the entries of the latest state preceded by the [`StateEntry::LastBlock`],
+    /// so the chain started from them after the regenesis continues this one.
+    pub fn regenesis_snapshot(
+        &self,
+    ) -> StorageResult<BoxedIter<'_, StorageResult<StateEntry>>> {
+        let last_block = self.last_block()?;
+        let state = self
+            .state_snapshot(None, SnapshotFilter::default())?
+            .filter(|entry| !matches!(entry, Ok(StateEntry::Height(_))));
+
+        Ok(iter::once(Ok(StateEntry::LastBlock(last_block)))
+            .chain(state)
+            .into_boxed())
+    }
+
+    /// Returns the latest block along with the root of all blocks up to it.
+    pub(crate) fn last_block(&self) -> StorageResult<LastBlockConfig> {
+        let height = self.latest_height()?;
+        Ok(LastBlockConfig {
+            height,
+            blocks_root: self.storage::<FuelBlocks>().root(&height)?.into(),
+        })
+    }
+
+    /// Iterates over the latest coins in the order of the snapshot.
+    pub(crate) fn snapshot_coins(&self) -> BoxedIter<'_, StorageResult<CompressedCoin>> {
+        StateView::latest(self)
+            .owned::<CompressedCoin>(Column::Coins, Column::OwnedCoins, vec![])
+            .map(|item| item.map(|(_, coin)| coin))
+            .into_boxed()
+    }
+
+    /// Iterates over the ids of the latest contracts in the order of the snapshot.
+    pub(crate) fn snapshot_contract_ids(
+        &self,
+    ) -> BoxedIter<'_, StorageResult<ContractId>> {
+        StateView::latest(self).contract_ids()
+    }
+
+    /// Iterates over the latest unspent messages in the order of the snapshot.
+    pub(crate) fn snapshot_messages(&self) -> BoxedIter<'_, StorageResult<Message>> {
+        StateView::latest(self).unspent_messages(vec![])
+    }
+}
+
+/// The state right after the block at the `height`, or the latest state.
+#[derive(Clone, Copy)]
+struct StateView<'a> {
+    database: &'a Database,
+    height: Option<BlockHeight>,
+}
+
+impl<'a> StateView<'a> {
+    fn latest(database: &'a Database) -> Self {
+        Self {
+            database,
+            height: None,
+        }
+    }
+
+    /// Iterates over the keys with the `prefix` and their raw values.
+    fn iter(
+        self,
+        column: Column,
+        prefix: &[u8],
+    ) -> BoxedIter<'a, DatabaseResult<(Vec<u8>, Vec<u8>)>> {
+        match self.height {
+            Some(height) => self.database.iter_at(column, prefix, height).into_boxed(),
+            None => {
+                let prefix = (!prefix.is_empty()).then_some(prefix);
+                self.database
+                    .data
+                    .iter_all(column, prefix, None, IterDirection::Forward)
+                    .map(|item| item.map(|(key, value)| (key, value.as_ref().clone())))
+                    .into_boxed()
+            }
+        }
+    }
+
+    fn raw_value(self, column: Column, key: &[u8]) -> DatabaseResult<Option<Vec<u8>>> {
+        match self.height {
+            Some(height) => self.database.raw_value_at(column, key, height),
+            None => Ok(self
+                .database
+                .data
+                .get(key, column)?
+                .map(|value| value.as_ref().clone())),
+        }
+    }
+
+    fn value<V: DeserializeOwned>(
+        self,
+        column: Column,
+        key: &[u8],
+    ) -> DatabaseResult<Option<V>> {
+        self.raw_value(column, key)?
+            .map(|value| decode(&value))
+            .transpose()
+    }
+
+    /// Iterates over the values of the `column` referenced by the keys of the `index`
+    /// with the `owners` prefixes, or over all values if there are no `owners`.
+    fn owned<V: DeserializeOwned>(
+        self,
+        column: Column,
+        index: Column,
+        owners: Vec<Address>,
+    ) -> BoxedIter<'a, StorageResult<(Vec<u8>, V)>> {
+        if owners.is_empty() {
+            return self
+                .iter(column, &[])
+                .map(|item| {
+                    let (key, value) = item?;
+                    Ok((key, decode(&value)?))
+                })
+                .into_boxed()
+        }
+
+        owners
+            .into_iter()
+            .flat_map(move |owner| self.iter(index, owner.as_ref()))
+            .map(move |item| {
+                let (index_key, _) = item?;
+                let key = index_key[Address::LEN..].to_vec();
+                let value = self.value(column, &key)?.ok_or(DatabaseError::Other(
+                    anyhow::anyhow!("The index {index:?} refers to the missing value"),
+                ))?;
+                Ok((key, value))
+            })
+            .into_boxed()
+    }
+
+    fn coins(
+        self,
+        filter: Rc<SnapshotFilter>,
+    ) -> BoxedIter<'a, StorageResult<StateEntry>> {
+        self.owned::<CompressedCoin>(Column::Coins, Column::OwnedCoins, filter.owners.clone())
+            .filter(move |item| {
+                !matches!(item, Ok((_, coin)) if !filter.includes_asset(&coin.asset_id))
+            })
+            .map(|item| {
+                let (key, coin) = item?;
+                Ok(StateEntry::Coin(coin_config(&key, coin)?))
+            })
+            .into_boxed()
+    }
+
+    fn contracts(
+        self,
+        filter: Rc<SnapshotFilter>,
+    ) -> BoxedIter<'a, StorageResult<StateEntry>> {
+        let contracts = if filter.contract_ids.is_empty() {
+            self.iter(Column::ContractsRawCode, &[])
+                .map(|item| {
+                    let (key, code) = item?;
+                    let contract_id = ContractId::new(
+                        key.as_slice().try_into().map_err(DatabaseError::from)?,
+                    );
+                    Ok((contract_id, code))
+                })
+                .into_boxed()
+        } else {
+            filter
+                .contract_ids
+                .clone()
+                .into_iter()
+                .map(move |contract_id| {
+                    let code = self
+                        .raw_value(Column::ContractsRawCode, contract_id.as_ref())?
+                        .ok_or(not_found!(ContractsRawCode))?;
+                    Ok((contract_id, code))
+                })
+                .into_boxed()
+        };
+
+        contracts
+            .flat_map(move |contract| match contract {
+                Ok((contract_id, code)) => {
+                    self.contract(contract_id, code, filter.clone())
+                }
+                Err(err) => iter::once(Err(err)).into_boxed(),
+            })
+            .into_boxed()
+    }
+
+    /// Returns the entry of the contract followed by the entries of its state
+    /// and balances.
+    fn contract(
+        self,
+        contract_id: ContractId,
+        code: Vec<u8>,
+        filter: Rc<SnapshotFilter>,
+    ) -> BoxedIter<'a, StorageResult<StateEntry>> {
+        let config = (|| -> StorageResult<_> {
+            let (salt, _): (Salt, Bytes32) = self
+                .value(Column::ContractsInfo, contract_id.as_ref())?
+                .ok_or(not_found!(ContractsInfo))?;
+            let utxo_info: ContractUtxoInfo = self
+                .value(Column::ContractsLatestUtxo, contract_id.as_ref())?
+                .ok_or(not_found!(ContractsLatestUtxo))?;
+            Ok(contract_config(contract_id, code, salt, utxo_info))
+        })();
+        let config = match config {
+            Ok(config) => config,
+            Err(err) => return iter::once(Err(err)).into_boxed(),
+        };
+
+        let state =
+            self.iter(Column::ContractsState, contract_id.as_ref())
+                .map(move |item| {
+                    let (key, value) = item?;
+                    Ok(StateEntry::ContractState(ContractStateConfig {
+                        contract_id,
+                        key: Bytes32::new(
+                            key[ContractId::LEN..]
+                                .try_into()
+                                .map_err(DatabaseError::from)?,
+                        ),
+                        value: decode(&value)?,
+                    }))
+                });
+        let balances = self
+            .iter(Column::ContractsAssets, contract_id.as_ref())
+            .map(move |item| {
+                let (key, value) = item?;
+                let asset_id = AssetId::new(
+                    key[ContractId::LEN..]
+                        .try_into()
+                        .map_err(DatabaseError::from)?,
+                );
+                let amount: Word = decode(&value)?;
+                Ok(ContractBalanceConfig {
+                    contract_id,
+                    asset_id,
+                    amount,
+                })
+            })
+            .filter(move |item| {
+                !matches!(item, Ok(balance) if !filter.includes_asset(&balance.asset_id))
+            })
+            .map(|item| item.map(StateEntry::ContractBalance));
+
+        iter::once(Ok(StateEntry::Contract(config)))
+            .chain(state)
+            .chain(balances)
+            .into_boxed()
+    }
+
+    fn messages(
+        self,
+        filter: Rc<SnapshotFilter>,
+    ) -> BoxedIter<'a, StorageResult<StateEntry>> {
+        self.unspent_messages(filter.owners.clone())
+            .map(|item| Ok(StateEntry::Message(MessageConfig::from(item?))))
+            .into_boxed()
+    }
+
+    /// Only unspent messages are the part of the state.
+    fn unspent_messages(
+        self,
+        owners: Vec<Address>,
+    ) -> BoxedIter<'a, StorageResult<Message>> {
+        self.owned::<Message>(Column::Messages, Column::OwnedMessageIds, owners)
+            .filter_map(move |item| {
+                let spent = item.as_ref().map(|(key, _)| {
+                    self.raw_value(Column::SpentMessages, key)
+                        .map(|spent| spent.is_some())
+                });
+                match spent {
+                    Ok(Ok(true)) => None,
+                    Ok(Ok(false)) => Some(item),
+                    Ok(Err(err)) => Some(Err(err.into())),
+                    Err(_) => Some(item),
+                }
+            })
+            .map(|item| item.map(|(_, message)| message))
+            .into_boxed()
+    }
+
+    fn contract_ids(self) -> BoxedIter<'a, StorageResult<ContractId>> {
+        self.iter(Column::ContractsRawCode, &[])
+            .map(|item| {
+                let (key, _) = item?;
+                Ok(ContractId::new(
+                    key.as_slice().try_into().map_err(DatabaseError::from)?,
+                ))
+            })
+            .into_boxed()
+    }
+}
+
+fn decode<V: DeserializeOwned>(value: &[u8]) -> DatabaseResult<V> {
+    postcard::from_bytes(value).map_err(|_| DatabaseError::Codec)
+}
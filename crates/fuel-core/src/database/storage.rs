@@ -69,16 +69,16 @@ impl Default for SparseMerkleMetadata {
     }
 }
 
-/// The table of fuel block's secondary key - `BlockHeight`.
-/// It links the `BlockHeight` to corresponding `BlockId`.
-pub struct FuelBlockSecondaryKeyBlockHeights;
+/// The table of fuel block's secondary key - `BlockId`.
+/// It links the `BlockId` to corresponding `BlockHeight`.
+pub struct FuelBlockIdsToHeights;
 
-impl Mappable for FuelBlockSecondaryKeyBlockHeights {
-    /// Secondary key - `BlockHeight`.
-    type Key = BlockHeight;
+impl Mappable for FuelBlockIdsToHeights {
+    /// Secondary key - `BlockId`.
+    type Key = BlockId;
     type OwnedKey = Self::Key;
-    /// Primary key - `BlockId`.
-    type Value = BlockId;
+    /// Primary key - `BlockHeight`.
+    type Value = BlockHeight;
     type OwnedValue = Self::Value;
 }
 
@@ -154,9 +154,9 @@ pub trait DatabaseColumn {
     fn column() -> Column;
 }
 
-impl DatabaseColumn for FuelBlockSecondaryKeyBlockHeights {
+impl DatabaseColumn for FuelBlockIdsToHeights {
     fn column() -> Column {
-        Column::FuelBlockSecondaryKeyBlockHeights
+        Column::FuelBlockIdsToHeights
     }
 }
 
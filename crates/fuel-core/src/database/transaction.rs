@@ -1,6 +1,13 @@
 use crate::{
-    database::Database,
-    state::in_memory::transaction::MemoryTransactionView,
+    database::{
+        Column,
+        Database,
+        Result as DatabaseResult,
+    },
+    state::{
+        in_memory::transaction::MemoryTransactionView,
+        Value,
+    },
 };
 use fuel_core_storage::{
     transactional::Transaction,
@@ -23,6 +30,17 @@ pub struct DatabaseTransaction {
     database: Database,
 }
 
+impl DatabaseTransaction {
+    /// Returns the keys of the `column` changed by the transaction with their values
+    /// before the transaction.
+    pub(crate) fn previous_values(
+        &self,
+        column: Column,
+    ) -> DatabaseResult<Vec<(Vec<u8>, Option<Value>)>> {
+        self.changes.previous_values(column)
+    }
+}
+
 impl AsRef<Database> for DatabaseTransaction {
     fn as_ref(&self) -> &Database {
         &self.database
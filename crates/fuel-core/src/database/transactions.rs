@@ -11,7 +11,17 @@ use fuel_core_storage::{
 use fuel_core_types::{
     self,
     fuel_tx::{
+        field::{
+            Inputs,
+            Outputs,
+        },
+        input::coin::{
+            CoinPredicate,
+            CoinSigned,
+        },
         Bytes32,
+        Input,
+        Output,
         Transaction,
         TxPointer,
     },
@@ -84,6 +94,18 @@ impl Database {
         )
     }
 
+    pub fn remove_tx_id_owner(
+        &self,
+        owner: &Address,
+        block_height: BlockHeight,
+        tx_idx: TransactionIndex,
+    ) -> DatabaseResult<Option<Bytes32>> {
+        self.remove(
+            &owned_tx_index_key(owner, block_height, tx_idx),
+            Column::TransactionsByOwnerBlockIdx,
+        )
+    }
+
     pub fn update_tx_status(
         &self,
         id: &Bytes32,
@@ -119,6 +141,41 @@ fn owned_tx_index_key(
     default
 }
 
+/// Returns the owners of the coins spent and created by the transaction.
+/// The transaction is indexed in the `TransactionsByOwnerBlockIdx` by each of them.
+pub fn transaction_owners(tx: &Transaction) -> Vec<Address> {
+    let (inputs, outputs) = match tx {
+        Transaction::Script(tx) => (tx.inputs().as_slice(), tx.outputs().as_slice()),
+        Transaction::Create(tx) => (tx.inputs().as_slice(), tx.outputs().as_slice()),
+        Transaction::Mint(tx) => (&[][..], tx.outputs().as_slice()),
+    };
+
+    let mut owners = vec![];
+    for input in inputs {
+        if let Input::CoinSigned(CoinSigned { owner, .. })
+        | Input::CoinPredicate(CoinPredicate { owner, .. }) = input
+        {
+            owners.push(*owner);
+        }
+    }
+
+    for output in outputs {
+        match output {
+            Output::Coin { to, .. }
+            | Output::Change { to, .. }
+            | Output::Variable { to, .. } => {
+                owners.push(*to);
+            }
+            Output::Contract { .. } | Output::ContractCreated { .. } => {}
+        }
+    }
+
+    // dedupe owners from inputs and outputs prior to indexing
+    owners.sort();
+    owners.dedup();
+    owners
+}
+
 ////////////////////////////////////// Not storage part //////////////////////////////////////
 
 pub type TransactionIndex = u16;
@@ -0,0 +1,509 @@
+//! The offline verification of the internal consistency of the database.
+//!
+//! The verification recomputes the data derived from the other columns
+//! and reports every mismatch, instead of failing on the first one.
+
+use crate::database::{
+    storage::{
+        DenseMerkleMetadata,
+        FuelBlockMerkleData,
+        SparseMerkleMetadata,
+    },
+    Column,
+    Database,
+    Result as DatabaseResult,
+};
+use fuel_core_storage::{
+    iter::IterDirection,
+    Result as StorageResult,
+};
+use fuel_core_types::{
+    blockchain::primitives::BlockId,
+    entities::{
+        coins::coin::CompressedCoin,
+        message::Message,
+    },
+    fuel_merkle::{
+        binary,
+        sparse::{
+            self,
+            MerkleTreeKey,
+        },
+    },
+    fuel_tx::{
+        Address,
+        Bytes32,
+        ContractId,
+    },
+    fuel_types::{
+        BlockHeight,
+        Word,
+    },
+};
+use serde::de::DeserializeOwned;
+use std::fmt;
+
+/// The inconsistency found by the [`Database::verify_integrity`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inconsistency {
+    /// The stored Merkle root of the contract's entries in the `column` doesn't match
+    /// the entries.
+    ContractRoot {
+        column: Column,
+        contract_id: ContractId,
+        stored: Bytes32,
+        computed: Bytes32,
+    },
+    /// The root node of the contract's Merkle tree is missing in the `column`.
+    MissingMerkleNodes {
+        column: Column,
+        contract_id: ContractId,
+    },
+    /// The owner index refers to the coin or message that doesn't exist
+    /// or belongs to another owner.
+    DanglingOwnerIndex {
+        column: Column,
+        owner: Address,
+        key: String,
+    },
+    /// The block id index refers to the block that doesn't exist.
+    MissingBlock { height: BlockHeight, id: BlockId },
+    /// The block is stored at the height that differs from its own height.
+    BlockHeightMismatch {
+        height: BlockHeight,
+        id: BlockId,
+        actual: BlockHeight,
+    },
+    /// The block isn't indexed by its id.
+    UnindexedBlock { height: BlockHeight, id: BlockId },
+    /// The block Merkle metadata at the height doesn't match the block ids.
+    BlockMerkleRoot { height: BlockHeight },
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inconsistency::ContractRoot {
+                column,
+                contract_id,
+                stored,
+                computed,
+            } => write!(
+                f,
+                "The {column:?} root of the contract {contract_id:#x} is {stored:#x}, but the entries hash to {computed:#x}"
+            ),
+            Inconsistency::MissingMerkleNodes {
+                column,
+                contract_id,
+            } => write!(
+                f,
+                "The Merkle tree root of the contract {contract_id:#x} is missing in the {column:?}"
+            ),
+            Inconsistency::DanglingOwnerIndex { column, owner, key } => write!(
+                f,
+                "The {column:?} entry of the owner {owner:#x} refers to the missing or foreign entity {key}"
+            ),
+            Inconsistency::MissingBlock { height, id } => write!(
+                f,
+                "The block {id:#x} at the height {height} doesn't exist"
+            ),
+            Inconsistency::BlockHeightMismatch { height, id, actual } => write!(
+                f,
+                "The block {id:#x} is stored at the height {height}, but its height is {actual}"
+            ),
+            Inconsistency::UnindexedBlock { height, id } => write!(
+                f,
+                "The block {id:#x} at the height {height} isn't indexed by its id"
+            ),
+            Inconsistency::BlockMerkleRoot { height } => write!(
+                f,
+                "The block Merkle metadata at the height {height} doesn't match the block ids"
+            ),
+        }
+    }
+}
+
+impl Database {
+    /// Verifies the internal consistency of the database and returns all found
+    /// inconsistencies. It reads the whole database, so it is intended for the
+    /// offline use.
+    pub fn verify_integrity(&self) -> StorageResult<Vec<Inconsistency>> {
+        let mut inconsistencies = vec![];
+        self.verify_contract_roots(&mut inconsistencies)?;
+        self.verify_owner_indexes(&mut inconsistencies)?;
+        self.verify_block_heights(&mut inconsistencies)?;
+        self.verify_block_merkle_tree(&mut inconsistencies)?;
+        Ok(inconsistencies)
+    }
+
+    fn verify_contract_roots(
+        &self,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> StorageResult<()> {
+        self.verify_contract_tree(
+            Column::ContractsState,
+            Column::ContractsStateMerkleMetadata,
+            Column::ContractsStateMerkleData,
+            |value: Bytes32| value.to_vec(),
+            inconsistencies,
+        )?;
+        self.verify_contract_tree(
+            Column::ContractsAssets,
+            Column::ContractsAssetsMerkleMetadata,
+            Column::ContractsAssetsMerkleData,
+            |balance: Word| balance.to_be_bytes().to_vec(),
+            inconsistencies,
+        )
+    }
+
+    /// Recomputes the sparse Merkle root of every contract from the entries of the
+    /// `column` keyed by `ContractId ++ key` and compares it with the stored metadata.
+    /// The entries are sorted by the key, so only one contract is kept in memory.
+    fn verify_contract_tree<V: DeserializeOwned>(
+        &self,
+        column: Column,
+        metadata_column: Column,
+        nodes_column: Column,
+        leaf: impl Fn(V) -> Vec<u8>,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> StorageResult<()> {
+        let mut verify =
+            |contract_id: ContractId, leaves: Vec<(MerkleTreeKey, Vec<u8>)>| {
+                let computed: Bytes32 =
+                    sparse::in_memory::MerkleTree::root_from_set(leaves.into_iter())
+                        .into();
+                let stored = self
+                    .get::<SparseMerkleMetadata>(contract_id.as_ref(), metadata_column)?
+                    .map(|metadata| Bytes32::from(metadata.root))
+                    .unwrap_or_else(|| Bytes32::from(*sparse::empty_sum()));
+                if stored != computed {
+                    inconsistencies.push(Inconsistency::ContractRoot {
+                        column,
+                        contract_id,
+                        stored,
+                        computed,
+                    });
+                } else if !self.contains_key(stored.as_ref(), nodes_column)? {
+                    inconsistencies.push(Inconsistency::MissingMerkleNodes {
+                        column: nodes_column,
+                        contract_id,
+                    });
+                }
+                DatabaseResult::Ok(())
+            };
+
+        let mut current: Option<(ContractId, Vec<_>)> = None;
+        for item in self.iter_all::<Vec<u8>, V>(column, Some(IterDirection::Forward)) {
+            let (key, value) = item?;
+            let (contract_id, key) = key.split_at(ContractId::LEN);
+            let contract_id =
+                ContractId::try_from(contract_id).expect("The key has the contract id");
+            let key = Bytes32::try_from(key).expect("The key has 32 bytes after the id");
+            let leaf = (MerkleTreeKey::new(key), leaf(value));
+
+            match &mut current {
+                Some((current_id, leaves)) if *current_id == contract_id => {
+                    leaves.push(leaf)
+                }
+                _ => {
+                    if let Some((contract_id, leaves)) =
+                        current.replace((contract_id, vec![leaf]))
+                    {
+                        verify(contract_id, leaves)?;
+                    }
+                }
+            }
+        }
+        if let Some((contract_id, leaves)) = current {
+            verify(contract_id, leaves)?;
+        }
+
+        // The metadata of the contracts without any entries.
+        for item in self.iter_all::<Vec<u8>, SparseMerkleMetadata>(
+            metadata_column,
+            Some(IterDirection::Forward),
+        ) {
+            let (contract_id, _) = item?;
+            let has_entries = self
+                .iter_all_by_prefix::<Vec<u8>, V, _>(column, Some(&contract_id))
+                .next()
+                .is_some();
+            if !has_entries {
+                let contract_id = ContractId::try_from(contract_id.as_slice())
+                    .expect("The key is the contract id");
+                verify(contract_id, vec![])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_owner_indexes(
+        &self,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> StorageResult<()> {
+        for item in self
+            .iter_all::<Vec<u8>, bool>(Column::OwnedCoins, Some(IterDirection::Forward))
+        {
+            let (key, _) = item?;
+            let (owner, utxo_id) = key.split_at(Address::LEN);
+            let owner = Address::try_from(owner).expect("The key has the owner");
+            let coin: Option<CompressedCoin> = self.get(utxo_id, Column::Coins)?;
+            if !matches!(coin, Some(coin) if coin.owner == owner) {
+                inconsistencies.push(Inconsistency::DanglingOwnerIndex {
+                    column: Column::OwnedCoins,
+                    owner,
+                    key: format!("coin 0x{}", hex::encode(utxo_id)),
+                });
+            }
+        }
+
+        for item in self.iter_all::<Vec<u8>, bool>(
+            Column::OwnedMessageIds,
+            Some(IterDirection::Forward),
+        ) {
+            let (key, _) = item?;
+            let (owner, nonce) = key.split_at(Address::LEN);
+            let owner = Address::try_from(owner).expect("The key has the owner");
+            let message: Option<Message> = self.get(nonce, Column::Messages)?;
+            if !matches!(message, Some(message) if message.recipient == owner) {
+                inconsistencies.push(Inconsistency::DanglingOwnerIndex {
+                    column: Column::OwnedMessageIds,
+                    owner,
+                    key: format!("message 0x{}", hex::encode(nonce)),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_block_heights(
+        &self,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> StorageResult<()> {
+        for item in self.iter_all::<Vec<u8>, BlockHeight>(
+            Column::FuelBlockIdsToHeights,
+            Some(IterDirection::Forward),
+        ) {
+            let (id, height) = item?;
+            let id: [u8; 32] = id.try_into().expect("The key is the block id");
+            let id = BlockId::from(id);
+            match self.get_block_id(&height)? {
+                Some(actual) if actual == id => {}
+                // The blocks pruned before they were keyed by the height are not stored.
+                None if self.is_pruned(&height)? => {}
+                _ => inconsistencies.push(Inconsistency::MissingBlock { height, id }),
+            }
+        }
+
+        for item in self.all_blocks(None, IterDirection::Forward) {
+            let (height, block) = item?;
+            let id = block.id();
+            let actual = *block.header().height();
+            if actual != height {
+                inconsistencies.push(Inconsistency::BlockHeightMismatch {
+                    height,
+                    id,
+                    actual,
+                });
+            }
+            if self.get_block_height(&id)? != Some(height) {
+                inconsistencies.push(Inconsistency::UnindexedBlock { height, id });
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_block_merkle_tree(
+        &self,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> StorageResult<()> {
+        let mut tree = binary::in_memory::MerkleTree::new();
+        let mut leaves_count = 0;
+        let mut latest = None;
+        for item in self.all_blocks(None, IterDirection::Forward) {
+            let (height, block) = item?;
+            tree.push(block.id().as_slice());
+            leaves_count += 1;
+
+            let metadata: Option<DenseMerkleMetadata> =
+                self.get(&height.to_bytes(), Column::FuelBlockMerkleMetadata)?;
+            let valid = matches!(
+                &metadata,
+                Some(metadata) if metadata.version == leaves_count && metadata.root == tree.root()
+            );
+            if !valid {
+                inconsistencies.push(Inconsistency::BlockMerkleRoot { height });
+            }
+            latest = Some(height);
+        }
+
+        // The persisted tree must produce the same root as the block ids.
+        if let Some(height) = latest {
+            let persisted =
+                binary::MerkleTree::<FuelBlockMerkleData, _>::load(self, leaves_count)
+                    .map(|persisted| persisted.root());
+            if persisted.ok() != Some(tree.root()) {
+                inconsistencies.push(Inconsistency::BlockMerkleRoot { height });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core_storage::{
+        tables::{
+            Coins,
+            ContractsAssets,
+            ContractsState,
+            FuelBlocks,
+        },
+        ContractsAssetKey,
+        ContractsStateKey,
+        StorageAsMut,
+        StorageAsRef,
+    };
+    use fuel_core_types::{
+        blockchain::{
+            block::{
+                CompressedBlock,
+                PartialFuelBlock,
+            },
+            header::{
+                ConsensusHeader,
+                PartialBlockHeader,
+            },
+            primitives::Empty,
+        },
+        fuel_tx::{
+            ConsensusParameters,
+            UtxoId,
+        },
+    };
+
+    const CONTRACT_ID: ContractId = ContractId::new([1; 32]);
+    const OWNER: Address = Address::new([5; 32]);
+
+    fn consistent_database() -> Database {
+        let mut database = Database::default();
+        database
+            .storage_as_mut::<ContractsState>()
+            .insert(
+                &ContractsStateKey::new(&CONTRACT_ID, &[2; 32].into()),
+                &[3; 32].into(),
+            )
+            .unwrap();
+        database
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&ContractsAssetKey::new(&CONTRACT_ID, &[4; 32].into()), &100)
+            .unwrap();
+        let coin = CompressedCoin {
+            owner: OWNER,
+            amount: 10,
+            asset_id: Default::default(),
+            maturity: Default::default(),
+            tx_pointer: Default::default(),
+        };
+        database
+            .storage_as_mut::<Coins>()
+            .insert(&UtxoId::new([6; 32].into(), 0), &coin)
+            .unwrap();
+
+        for height in 0..3u32 {
+            let header = PartialBlockHeader {
+                application: Default::default(),
+                consensus: ConsensusHeader::<Empty> {
+                    height: height.into(),
+                    ..Default::default()
+                },
+            };
+            let block = PartialFuelBlock::new(header, vec![]).generate(&[]);
+            database
+                .storage_as_mut::<FuelBlocks>()
+                .insert(
+                    &height.into(),
+                    &block.compress(&ConsensusParameters::DEFAULT.chain_id),
+                )
+                .unwrap();
+        }
+        database
+    }
+
+    #[test]
+    fn consistent_database_has_no_inconsistencies() {
+        let database = consistent_database();
+        assert_eq!(database.verify_integrity().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn changed_contract_state_is_detected() {
+        let database = consistent_database();
+        // Bypass the storage to leave the Merkle metadata outdated.
+        let key = ContractsStateKey::new(&CONTRACT_ID, &[2; 32].into());
+        let _: Option<Bytes32> = database
+            .insert(
+                key.as_ref(),
+                Column::ContractsState,
+                &Bytes32::from([7; 32]),
+            )
+            .unwrap();
+
+        let inconsistencies = database.verify_integrity().unwrap();
+        assert!(matches!(
+            inconsistencies.as_slice(),
+            [Inconsistency::ContractRoot { column: Column::ContractsState, contract_id, .. }]
+                if *contract_id == CONTRACT_ID
+        ));
+    }
+
+    #[test]
+    fn dangling_owned_coin_is_detected() {
+        let database = consistent_database();
+        let utxo_id = UtxoId::new([6; 32].into(), 0);
+        let key = [&[6; 32][..], &[0]].concat();
+        let _: Option<CompressedCoin> = database.remove(&key, Column::Coins).unwrap();
+        assert!(!database.storage::<Coins>().contains_key(&utxo_id).unwrap());
+
+        let inconsistencies = database.verify_integrity().unwrap();
+        assert!(matches!(
+            inconsistencies.as_slice(),
+            [Inconsistency::DanglingOwnerIndex { column: Column::OwnedCoins, owner, .. }] if *owner == OWNER
+        ));
+    }
+
+    #[test]
+    fn broken_block_indexes_are_detected() {
+        let database = consistent_database();
+        let height = BlockHeight::from(1u32);
+        let id = database.get_block_id(&height).unwrap().unwrap();
+        let _: Option<BlockHeight> = database
+            .remove(id.as_slice(), Column::FuelBlockIdsToHeights)
+            .unwrap();
+
+        let inconsistencies = database.verify_integrity().unwrap();
+        assert!(matches!(
+            inconsistencies.as_slice(),
+            [Inconsistency::UnindexedBlock { height: h, .. }] if *h == height
+        ));
+    }
+
+    #[test]
+    fn missing_block_is_detected() {
+        let database = consistent_database();
+        let height = BlockHeight::from(1u32);
+        let _: Option<CompressedBlock> = database
+            .remove(&height.to_bytes(), Column::FuelBlocks)
+            .unwrap();
+
+        let inconsistencies = database.verify_integrity().unwrap();
+        assert!(inconsistencies.iter().any(
+            |i| matches!(i, Inconsistency::MissingBlock { height: h, .. } if *h == height)
+        ));
+        // The block ids don't produce the stored Merkle roots without the missing block.
+        assert!(inconsistencies
+            .iter()
+            .any(|i| matches!(i, Inconsistency::BlockMerkleRoot { .. })));
+    }
+}
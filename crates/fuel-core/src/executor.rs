@@ -1,6 +1,9 @@
 use crate::database::{
     transaction::DatabaseTransaction,
-    transactions::TransactionIndex,
+    transactions::{
+        transaction_owners,
+        TransactionIndex,
+    },
     vm_database::VmDatabase,
     Database,
 };
@@ -45,7 +48,6 @@ use fuel_core_types::{
     },
     fuel_tx::{
         field::{
-            Inputs,
             Outputs,
             TxPointer as TxPointerField,
         },
@@ -460,6 +462,12 @@ where
         // save the associated owner for each transaction in the block
         self.index_tx_owners_for_block(&result.block, &mut block_db_transaction)?;
 
+        // save the state before the block to be able to query it at the past heights
+        if self.config.archive {
+            block_db_transaction
+                .archive_previous_values(*result.block.header().height())?;
+        }
+
         // ------------ GraphQL API Functionality   END ------------
 
         // insert block into database
@@ -467,7 +475,7 @@ where
             .deref_mut()
             .storage::<FuelBlocks>()
             .insert(
-                &finalized_block_id,
+                result.block.header().height(),
                 &result
                     .block
                     .compress(&self.config.transaction_parameters.chain_id),
@@ -1552,79 +1560,19 @@ where
         block: &Block,
         block_db_transaction: &mut DatabaseTransaction,
     ) -> ExecutorResult<()> {
+        let block_height = *block.header().height();
         for (tx_idx, tx) in block.transactions().iter().enumerate() {
-            let block_height = *block.header().height();
-            let mut inputs = &[][..];
-            let outputs;
             let tx_id = tx.id(&self.config.transaction_parameters.chain_id);
-            match tx {
-                Transaction::Script(tx) => {
-                    inputs = tx.inputs().as_slice();
-                    outputs = tx.outputs().as_slice();
-                }
-                Transaction::Create(tx) => {
-                    inputs = tx.inputs().as_slice();
-                    outputs = tx.outputs().as_slice();
-                }
-                Transaction::Mint(tx) => {
-                    outputs = tx.outputs().as_slice();
-                }
-            }
-            self.persist_owners_index(
-                block_height,
-                inputs,
-                outputs,
-                &tx_id,
-                tx_idx as u16,
-                block_db_transaction.deref_mut(),
-            )?;
-        }
-        Ok(())
-    }
-
-    /// Index the tx id by owner for all of the inputs and outputs
-    fn persist_owners_index(
-        &self,
-        block_height: BlockHeight,
-        inputs: &[Input],
-        outputs: &[Output],
-        tx_id: &Bytes32,
-        tx_idx: u16,
-        db: &mut Database,
-    ) -> ExecutorResult<()> {
-        let mut owners = vec![];
-        for input in inputs {
-            if let Input::CoinSigned(CoinSigned { owner, .. })
-            | Input::CoinPredicate(CoinPredicate { owner, .. }) = input
-            {
-                owners.push(owner);
-            }
-        }
-
-        for output in outputs {
-            match output {
-                Output::Coin { to, .. }
-                | Output::Change { to, .. }
-                | Output::Variable { to, .. } => {
-                    owners.push(to);
-                }
-                Output::Contract { .. } | Output::ContractCreated { .. } => {}
+            // index the tx id by owner for all of the inputs and outputs
+            for owner in transaction_owners(tx) {
+                block_db_transaction.record_tx_id_owner(
+                    &owner,
+                    block_height,
+                    tx_idx as TransactionIndex,
+                    &tx_id,
+                )?;
             }
         }
-
-        // dedupe owners from inputs and outputs prior to indexing
-        owners.sort();
-        owners.dedup();
-
-        for owner in owners {
-            db.record_tx_id_owner(
-                owner,
-                block_height,
-                tx_idx as TransactionIndex,
-                tx_id,
-            )?;
-        }
-
         Ok(())
     }
 
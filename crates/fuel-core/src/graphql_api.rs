@@ -8,7 +8,10 @@ use fuel_core_types::{
     fuel_vm::GasCosts,
     secrecy::Secret,
 };
-use std::net::SocketAddr;
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+};
 
 pub mod ports;
 #[cfg(feature = "metrics")]
@@ -27,6 +30,12 @@ pub struct Config {
     pub transaction_parameters: ConsensusParameters,
     pub gas_costs: GasCosts,
     pub consensus_key: Option<Secret<SecretKeyWrapper>>,
+    /// The directory for the database checkpoints created via the API.
+    /// The checkpoints are disabled if it is `None`.
+    pub database_checkpoint_dir: Option<PathBuf>,
+    /// Reject the mutations that submit the transactions, because the node serves
+    /// only the queries from the read-only secondary instance of the database.
+    pub read_only: bool,
 }
 
 pub trait IntoApiResult<T> {
@@ -13,6 +13,7 @@ use fuel_core_storage::{
         ContractsAssets,
         ContractsInfo,
         ContractsRawCode,
+        ContractsState,
         FuelBlocks,
         Messages,
         Receipts,
@@ -26,14 +27,21 @@ use fuel_core_storage::{
 };
 use fuel_core_txpool::service::TxStatusMessage;
 use fuel_core_types::{
-    blockchain::primitives::{
-        BlockId,
-        DaBlockHeight,
+    blockchain::{
+        block::CompressedBlock,
+        primitives::{
+            BlockId,
+            DaBlockHeight,
+        },
     },
-    entities::message::{
-        MerkleProof,
-        Message,
+    entities::{
+        coins::coin::CompressedCoin,
+        message::{
+            MerkleProof,
+            Message,
+        },
     },
+    fuel_asm::Word,
     fuel_tx::{
         Receipt,
         Transaction,
@@ -45,6 +53,7 @@ use fuel_core_types::{
         Address,
         AssetId,
         BlockHeight,
+        Bytes32,
         ContractId,
         Nonce,
     },
@@ -57,7 +66,10 @@ use fuel_core_types::{
     },
     tai64::Tai64,
 };
-use std::sync::Arc;
+use std::{
+    path::Path,
+    sync::Arc,
+};
 
 /// The database port expected by GraphQL API service.
 pub trait DatabasePort:
@@ -70,6 +82,8 @@ pub trait DatabasePort:
     + DatabaseContracts
     + DatabaseChain
     + DatabaseMessageProof
+    + DatabaseArchive
+    + DatabaseCheckpoint
 {
 }
 
@@ -80,13 +94,18 @@ pub trait DatabaseBlocks:
 {
     fn block_id(&self, height: &BlockHeight) -> StorageResult<BlockId>;
 
-    fn blocks_ids(
+    fn block_height(&self, id: &BlockId) -> StorageResult<BlockHeight>;
+
+    fn blocks(
         &self,
         start: Option<BlockHeight>,
         direction: IterDirection,
-    ) -> BoxedIter<'_, StorageResult<(BlockHeight, BlockId)>>;
+    ) -> BoxedIter<'_, StorageResult<(BlockHeight, CompressedBlock)>>;
 
     fn ids_of_latest_block(&self) -> StorageResult<(BlockHeight, BlockId)>;
+
+    /// Returns the height of the last pruned block, if any block was pruned.
+    fn pruned_height(&self) -> StorageResult<Option<BlockHeight>>;
 }
 
 /// Trait that specifies all the getters required for transactions.
@@ -138,6 +157,7 @@ pub trait DatabaseContracts:
     StorageInspect<ContractsRawCode, Error = StorageError>
     + StorageInspect<ContractsInfo, Error = StorageError>
     + StorageInspect<ContractsAssets, Error = StorageError>
+    + StorageInspect<ContractsState, Error = StorageError>
 {
     fn contract_balances(
         &self,
@@ -147,6 +167,42 @@ pub trait DatabaseContracts:
     ) -> BoxedIter<StorageResult<ContractBalance>>;
 }
 
+/// Trait that specifies all the getters of the historical state recorded in the archive mode.
+/// Each getter returns the state right after the block at the `height`.
+pub trait DatabaseArchive {
+    fn archived_contract_balance(
+        &self,
+        contract: &ContractId,
+        asset: &AssetId,
+        height: BlockHeight,
+    ) -> StorageResult<Option<Word>>;
+
+    fn archived_contract_state(
+        &self,
+        contract: &ContractId,
+        key: &Bytes32,
+        height: BlockHeight,
+    ) -> StorageResult<Option<Bytes32>>;
+
+    fn archived_owned_coins(
+        &self,
+        owner: &Address,
+        height: BlockHeight,
+    ) -> StorageResult<Vec<CompressedCoin>>;
+
+    fn archived_owned_messages(
+        &self,
+        owner: &Address,
+        height: BlockHeight,
+    ) -> StorageResult<Vec<Message>>;
+}
+
+/// Trait that specifies the maintenance operations over the database.
+pub trait DatabaseCheckpoint {
+    /// Creates a consistent checkpoint of the database at the `path`.
+    fn checkpoint(&self, path: &Path) -> StorageResult<()>;
+}
+
 /// Trait that specifies all the getters required for chain metadata.
 pub trait DatabaseChain {
     fn chain_name(&self) -> StorageResult<String>;
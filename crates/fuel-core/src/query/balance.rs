@@ -17,6 +17,7 @@ use fuel_core_types::{
         Address,
         AssetId,
     },
+    fuel_types::BlockHeight,
     services::graphql_api::AddressBalance,
 };
 use itertools::Itertools;
@@ -31,6 +32,13 @@ pub trait BalanceQueryData: Send + Sync {
     fn balance(&self, owner: Address, asset_id: AssetId)
         -> StorageResult<AddressBalance>;
 
+    fn balance_at(
+        &self,
+        owner: Address,
+        asset_id: AssetId,
+        height: BlockHeight,
+    ) -> StorageResult<AddressBalance>;
+
     fn balances(
         &self,
         owner: Address,
@@ -68,6 +76,37 @@ impl BalanceQueryData for Database {
         })
     }
 
+    fn balance_at(
+        &self,
+        owner: Address,
+        asset_id: AssetId,
+        height: BlockHeight,
+    ) -> StorageResult<AddressBalance> {
+        let coins_amount: u64 = self
+            .archived_owned_coins(&owner, height)?
+            .into_iter()
+            .filter(|coin| coin.asset_id == asset_id)
+            .map(|coin| coin.amount)
+            .sum();
+
+        // Only the messages without data are spendable as the base asset coins.
+        let messages_amount: u64 = if asset_id == AssetId::BASE {
+            self.archived_owned_messages(&owner, height)?
+                .into_iter()
+                .filter(|message| message.data.is_empty())
+                .map(|message| message.amount)
+                .sum()
+        } else {
+            0
+        };
+
+        Ok(AddressBalance {
+            owner,
+            amount: coins_amount + messages_amount,
+            asset_id,
+        })
+    }
+
     fn balances(
         &self,
         owner: Address,
@@ -1,4 +1,5 @@
 use crate::graphql_api::ports::DatabasePort;
+use fuel_core_database::Error as DatabaseError;
 use fuel_core_storage::{
     iter::{
         BoxedIter,
@@ -10,6 +11,7 @@ use fuel_core_storage::{
         FuelBlocks,
         SealedBlockConsensus,
     },
+    Error as StorageError,
     Result as StorageResult,
     StorageAsRef,
 };
@@ -21,6 +23,10 @@ use fuel_core_types::{
     },
     fuel_types::BlockHeight,
 };
+use std::{
+    borrow::Cow,
+    iter,
+};
 
 pub trait SimpleBlockData: Send + Sync {
     fn block(&self, id: &BlockId) -> StorageResult<CompressedBlock>;
@@ -28,13 +34,27 @@ pub trait SimpleBlockData: Send + Sync {
 
 impl<D: DatabasePort + ?Sized> SimpleBlockData for D {
     fn block(&self, id: &BlockId) -> StorageResult<CompressedBlock> {
-        let block = self
-            .storage::<FuelBlocks>()
-            .get(id)?
-            .ok_or_else(|| not_found!(FuelBlocks))?
-            .into_owned();
-
-        Ok(block)
+        let height = match self.block_height(id) {
+            Ok(height) => height,
+            Err(StorageError::NotFound(_, _)) => return Err(not_found!(FuelBlocks)),
+            Err(err) => return Err(err),
+        };
+
+        // The headers of the pruned blocks are kept, so the block is known.
+        match self.pruned_height()? {
+            Some(pruned_height) if height <= pruned_height => {
+                Err(DatabaseError::Pruned {
+                    entity: "FuelBlocks",
+                    pruned_height: *pruned_height,
+                }
+                .into())
+            }
+            _ => self
+                .storage::<FuelBlocks>()
+                .get(&height)?
+                .map(Cow::into_owned)
+                .ok_or(not_found!(FuelBlocks)),
+        }
     }
 }
 
@@ -53,7 +73,7 @@ pub trait BlockQueryData: Send + Sync + SimpleBlockData {
         direction: IterDirection,
     ) -> BoxedIter<StorageResult<CompressedBlock>>;
 
-    fn consensus(&self, id: &BlockId) -> StorageResult<Consensus>;
+    fn consensus(&self, height: &BlockHeight) -> StorageResult<Consensus>;
 }
 
 impl<D: DatabasePort + ?Sized> BlockQueryData for D {
@@ -70,7 +90,11 @@ impl<D: DatabasePort + ?Sized> BlockQueryData for D {
     }
 
     fn latest_block(&self) -> StorageResult<CompressedBlock> {
-        self.latest_block_id().and_then(|id| self.block(&id))
+        self.blocks(None, IterDirection::Reverse)
+            .next()
+            .transpose()?
+            .map(|(_, block)| block)
+            .ok_or(not_found!(FuelBlocks))
     }
 
     fn compressed_blocks(
@@ -78,20 +102,26 @@ impl<D: DatabasePort + ?Sized> BlockQueryData for D {
         start: Option<BlockHeight>,
         direction: IterDirection,
     ) -> BoxedIter<StorageResult<CompressedBlock>> {
-        self.blocks_ids(start.map(Into::into), direction)
-            .map(|result| {
-                result.and_then(|(_, id)| {
-                    let block = self.block(&id)?;
-
-                    Ok(block)
-                })
+        let pruned_height = match self.pruned_height() {
+            Ok(pruned_height) => pruned_height,
+            Err(err) => return iter::once(Err(err)).into_boxed(),
+        };
+
+        self.blocks(start, direction)
+            // The pruned blocks are skipped during the iteration.
+            .filter(move |result| {
+                !matches!(
+                    (result, pruned_height),
+                    (Ok((height, _)), Some(pruned_height)) if *height <= pruned_height
+                )
             })
+            .map(|result| result.map(|(_, block)| block))
             .into_boxed()
     }
 
-    fn consensus(&self, id: &BlockId) -> StorageResult<Consensus> {
+    fn consensus(&self, height: &BlockHeight) -> StorageResult<Consensus> {
         self.storage::<SealedBlockConsensus>()
-            .get(id)
+            .get(height)
             .map(|c| c.map(|c| c.into_owned()))?
             .ok_or(not_found!(SealedBlockConsensus))
     }
@@ -9,6 +9,7 @@ use fuel_core_storage::{
         ContractsAssets,
         ContractsInfo,
         ContractsRawCode,
+        ContractsState,
     },
     Result as StorageResult,
     StorageAsRef,
@@ -16,6 +17,8 @@ use fuel_core_storage::{
 use fuel_core_types::{
     fuel_types::{
         AssetId,
+        BlockHeight,
+        Bytes32,
         ContractId,
     },
     fuel_vm::Salt,
@@ -35,6 +38,26 @@ pub trait ContractQueryData: Send + Sync {
         asset_id: AssetId,
     ) -> StorageResult<ContractBalance>;
 
+    fn contract_balance_at(
+        &self,
+        contract_id: ContractId,
+        asset_id: AssetId,
+        height: BlockHeight,
+    ) -> StorageResult<ContractBalance>;
+
+    fn contract_state(
+        &self,
+        contract_id: ContractId,
+        key: Bytes32,
+    ) -> StorageResult<Bytes32>;
+
+    fn contract_state_at(
+        &self,
+        contract_id: ContractId,
+        key: Bytes32,
+        height: BlockHeight,
+    ) -> StorageResult<Bytes32>;
+
     fn contract_balances(
         &self,
         contract_id: ContractId,
@@ -91,6 +114,47 @@ impl<D: DatabasePort + ?Sized> ContractQueryData for D {
         })
     }
 
+    fn contract_balance_at(
+        &self,
+        contract_id: ContractId,
+        asset_id: AssetId,
+        height: BlockHeight,
+    ) -> StorageResult<ContractBalance> {
+        let amount = self
+            .archived_contract_balance(&contract_id, &asset_id, height)?
+            .ok_or(not_found!(ContractsAssets))?;
+
+        Ok(ContractBalance {
+            owner: contract_id,
+            amount,
+            asset_id,
+        })
+    }
+
+    fn contract_state(
+        &self,
+        contract_id: ContractId,
+        key: Bytes32,
+    ) -> StorageResult<Bytes32> {
+        let value = self
+            .storage::<ContractsState>()
+            .get(&(&contract_id, &key).into())?
+            .ok_or(not_found!(ContractsState))?
+            .into_owned();
+
+        Ok(value)
+    }
+
+    fn contract_state_at(
+        &self,
+        contract_id: ContractId,
+        key: Bytes32,
+        height: BlockHeight,
+    ) -> StorageResult<Bytes32> {
+        self.archived_contract_state(&contract_id, &key, height)?
+            .ok_or(not_found!(ContractsState))
+    }
+
     fn contract_balances(
         &self,
         contract_id: ContractId,
@@ -19,6 +19,7 @@ use fuel_core_storage::{
 };
 use itertools::Itertools;
 
+pub mod admin;
 pub mod balance;
 pub mod block;
 pub mod chain;
@@ -65,11 +66,16 @@ pub struct Query(
 
 #[cfg(feature = "dap")]
 #[derive(MergedObject, Default)]
-pub struct Mutation(dap::DapMutation, tx::TxMutation, block::BlockMutation);
+pub struct Mutation(
+    dap::DapMutation,
+    tx::TxMutation,
+    block::BlockMutation,
+    admin::AdminMutation,
+);
 
 #[cfg(not(feature = "dap"))]
 #[derive(MergedObject, Default)]
-pub struct Mutation(tx::TxMutation, block::BlockMutation);
+pub struct Mutation(tx::TxMutation, block::BlockMutation, admin::AdminMutation);
 
 #[derive(MergedSubscription, Default)]
 pub struct Subscription(tx::TxStatusSubscription);
@@ -0,0 +1,47 @@
+use crate::{
+    fuel_core_graphql_api::{
+        service::Database,
+        Config as GraphQLConfig,
+    },
+    query::BlockQueryData,
+};
+use anyhow::anyhow;
+use async_graphql::{
+    Context,
+    Object,
+};
+use std::time::{
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+#[derive(Default)]
+pub struct AdminMutation;
+
+#[Object]
+impl AdminMutation {
+    /// Creates a consistent checkpoint of the database without stopping the node and
+    /// returns its path. The checkpoint is created in the directory configured by
+    /// the `--db-checkpoint-dir` and can be restored with the `fuel-core db restore`.
+    async fn create_database_checkpoint(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<String> {
+        let query: &Database = ctx.data_unchecked();
+        let config = ctx.data_unchecked::<GraphQLConfig>();
+
+        let Some(checkpoint_dir) = &config.database_checkpoint_dir else {
+            return Err(anyhow!(
+                "The checkpoint directory must be configured to use this endpoint"
+            )
+            .into())
+        };
+
+        let height = query.latest_block_height()?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        let path = checkpoint_dir.join(format!("checkpoint-{}-{}", *height, timestamp));
+        query.checkpoint(&path)?;
+
+        Ok(path.display().to_string())
+    }
+}
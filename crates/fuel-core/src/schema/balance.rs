@@ -4,6 +4,7 @@ use crate::{
     schema::scalars::{
         Address,
         AssetId,
+        U32,
         U64,
     },
 };
@@ -52,9 +53,17 @@ impl BalanceQuery {
         ctx: &Context<'_>,
         #[graphql(desc = "address of the owner")] owner: Address,
         #[graphql(desc = "asset_id of the coin")] asset_id: AssetId,
+        #[graphql(
+            desc = "The height of the block after which the balance is returned. Requires the archive mode"
+        )]
+        block_height: Option<U32>,
     ) -> async_graphql::Result<Balance> {
         let data: &Database = ctx.data_unchecked();
-        let balance = data.balance(owner.0, asset_id.0)?.into();
+        let balance = match block_height {
+            Some(height) => data.balance_at(owner.0, asset_id.0, height.into())?,
+            None => data.balance(owner.0, asset_id.0)?,
+        }
+        .into();
         Ok(balance)
     }
 
@@ -97,8 +97,8 @@ impl Block {
 
     async fn consensus(&self, ctx: &Context<'_>) -> async_graphql::Result<Consensus> {
         let query: &Database = ctx.data_unchecked();
-        let id = self.0.header().id();
-        let consensus = query.consensus(&id)?;
+        let height = self.0.header().height();
+        let consensus = query.consensus(height)?;
 
         Ok(consensus.into())
     }
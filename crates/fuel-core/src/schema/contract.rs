@@ -6,9 +6,11 @@ use crate::{
     query::ContractQueryData,
     schema::scalars::{
         AssetId,
+        Bytes32,
         ContractId,
         HexString,
         Salt,
+        U32,
         U64,
     },
 };
@@ -71,6 +73,25 @@ impl ContractQuery {
         let data: &Database = ctx.data_unchecked();
         data.contract_id(id.0).into_api_result()
     }
+
+    /// Returns the value of the storage slot of the contract, or `null` if the slot is not set.
+    async fn contract_storage_slot(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "ID of the Contract")] contract: ContractId,
+        #[graphql(desc = "Key of the storage slot")] key: Bytes32,
+        #[graphql(
+            desc = "The height of the block after which the value is returned. Requires the archive mode"
+        )]
+        block_height: Option<U32>,
+    ) -> async_graphql::Result<Option<Bytes32>> {
+        let data: &Database = ctx.data_unchecked();
+        let value = match block_height {
+            Some(height) => data.contract_state_at(contract.0, key.0, height.into()),
+            None => data.contract_state(contract.0, key.0),
+        };
+        value.into_api_result()
+    }
 }
 
 pub struct ContractBalance(graphql_api::ContractBalance);
@@ -106,23 +127,30 @@ impl ContractBalanceQuery {
         ctx: &Context<'_>,
         contract: ContractId,
         asset: AssetId,
+        #[graphql(
+            desc = "The height of the block after which the balance is returned. Requires the archive mode"
+        )]
+        block_height: Option<U32>,
     ) -> async_graphql::Result<ContractBalance> {
         let contract_id = contract.into();
         let asset_id = asset.into();
         let context: &Database = ctx.data_unchecked();
-        context
-            .contract_balance(contract_id, asset_id)
-            .into_api_result()
-            .map(|result| {
-                result.unwrap_or_else(|| {
-                    graphql_api::ContractBalance {
-                        owner: contract_id,
-                        amount: 0,
-                        asset_id,
-                    }
-                    .into()
-                })
+        let balance = match block_height {
+            Some(height) => {
+                context.contract_balance_at(contract_id, asset_id, height.into())
+            }
+            None => context.contract_balance(contract_id, asset_id),
+        };
+        balance.into_api_result().map(|result| {
+            result.unwrap_or_else(|| {
+                graphql_api::ContractBalance {
+                    owner: contract_id,
+                    amount: 0,
+                    asset_id,
+                }
+                .into()
             })
+        })
     }
 
     async fn contract_balances(
@@ -251,6 +251,7 @@ impl TxMutation {
     ) -> async_graphql::Result<Transaction> {
         let txpool = ctx.data_unchecked::<TxPool>();
         let config = ctx.data_unchecked::<Config>();
+        ensure_writable(config)?;
         let tx = FuelTx::from_bytes(&tx.0)?;
         // TODO: use spawn_blocking here
         let _: Vec<_> = txpool
@@ -319,6 +320,7 @@ impl TxStatusSubscription {
     > {
         let txpool = ctx.data_unchecked::<TxPool>();
         let config = ctx.data_unchecked::<Config>();
+        ensure_writable(config)?;
         let tx = FuelTx::from_bytes(&tx.0)?;
         let tx_id = tx.id(&config.transaction_parameters.chain_id);
         let subscription = txpool.tx_update_subscribe(tx_id).await;
@@ -344,3 +346,15 @@ impl TxStatusSubscription {
             .take(1))
     }
 }
+
+/// The read-only node can't accept the transactions, because it doesn't run the
+/// transaction pool.
+fn ensure_writable(config: &Config) -> async_graphql::Result<()> {
+    if config.read_only {
+        return Err(anyhow!(
+            "The node serves only the queries, submit the transaction to the block producing node"
+        )
+        .into())
+    }
+    Ok(())
+}
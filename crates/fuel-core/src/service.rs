@@ -2,6 +2,7 @@ use crate::{
     database::Database,
     service::adapters::P2PAdapter,
 };
+use fuel_core_poa::Trigger;
 use fuel_core_services::{
     RunnableService,
     RunnableTask,
@@ -27,7 +28,9 @@ pub mod adapters;
 pub mod config;
 pub mod genesis;
 pub mod metrics;
+pub mod pruner;
 mod query;
+pub mod secondary;
 pub mod sub_services;
 
 #[derive(Clone)]
@@ -67,7 +70,12 @@ impl FuelService {
     /// Creates a `FuelService` instance from service config
     #[tracing::instrument(skip_all, fields(name = %config.name))]
     pub fn new(database: Database, mut config: Config) -> anyhow::Result<Self> {
-        database.init(&config.chain_conf)?;
+        if config.read_only_secondary.is_some() {
+            // The primary instance initializes the database.
+            database.verify_secondary(&config.chain_conf.chain_name)?;
+        } else {
+            database.init(&config.chain_conf)?;
+        }
         Self::make_config_consistent(&mut config);
         let task = Task::new(database, config)?;
         let runner = ServiceRunner::new(task);
@@ -86,16 +94,34 @@ impl FuelService {
         let database = match config.database_type {
             #[cfg(feature = "rocksdb")]
             DbType::RocksDb => {
-                // use a default tmp rocksdb if no path is provided
-                if config.database_path.as_os_str().is_empty() {
+                if config.read_only_secondary.is_some() {
+                    if config.database_path.as_os_str().is_empty() {
+                        anyhow::bail!(
+                            "The read-only secondary instance requires the path of the primary database"
+                        );
+                    }
+                    Database::open_secondary(
+                        &config.database_path,
+                        config.max_database_cache_size,
+                        &config.database_config,
+                    )?
+                } else if config.database_path.as_os_str().is_empty() {
+                    // use a default tmp rocksdb if no path is provided
                     warn!(
                         "No RocksDB path configured, initializing database with a tmp directory"
                     );
                     Database::default()
                 } else {
-                    Database::open(&config.database_path, config.max_database_cache_size)?
+                    Database::open_with_config(
+                        &config.database_path,
+                        config.max_database_cache_size,
+                        &config.database_config,
+                    )?
                 }
             }
+            DbType::InMemory if config.read_only_secondary.is_some() => {
+                anyhow::bail!("The in-memory database can't have a secondary instance")
+            }
             DbType::InMemory => Database::in_memory(),
             #[cfg(not(feature = "rocksdb"))]
             _ => Database::in_memory(),
@@ -147,6 +173,33 @@ impl FuelService {
             warn!("The `utxo_validation` of `BlockProducer` was inconsistent");
             config.block_producer.utxo_validation = config.utxo_validation;
         }
+        if config.read_only_secondary.is_some() {
+            Self::make_config_read_only(config);
+        }
+    }
+
+    /// Disables everything that writes to the database, the secondary instance
+    /// serves only the queries.
+    fn make_config_read_only(config: &mut Config) {
+        if config.block_production != Trigger::Never || config.manual_blocks_enabled {
+            warn!("The block production is disabled on the read-only secondary instance");
+            config.block_production = Trigger::Never;
+            config.manual_blocks_enabled = false;
+        }
+        if config.block_pruning.is_some() {
+            warn!("The block pruning is disabled on the read-only secondary instance");
+            config.block_pruning = None;
+        }
+        #[cfg(feature = "p2p")]
+        if config.p2p.is_some() {
+            warn!("The P2P network is disabled on the read-only secondary instance");
+            config.p2p = None;
+        }
+        #[cfg(feature = "relayer")]
+        if config.relayer.eth_client.is_some() {
+            warn!("The relayer is disabled on the read-only secondary instance");
+            config.relayer.eth_client = None;
+        }
     }
 }
 
@@ -197,8 +250,11 @@ pub struct Task {
 impl Task {
     /// Private inner method for initializing the fuel service task
     pub fn new(database: Database, config: Config) -> anyhow::Result<Task> {
-        // initialize state
-        genesis::maybe_initialize_state(&config, &database)?;
+        // initialize state, the secondary instance uses the state of the primary one
+        if config.read_only_secondary.is_none() {
+            genesis::maybe_initialize_state(&config, &database)?;
+            database.set_archive_mode(config.archive)?;
+        }
 
         // initialize sub services
         let (services, shared) = sub_services::init_sub_services(&config, &database)?;
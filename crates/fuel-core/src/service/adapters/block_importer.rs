@@ -27,10 +27,7 @@ use fuel_core_types::{
     blockchain::{
         block::Block,
         consensus::Consensus,
-        primitives::{
-            BlockId,
-            DaBlockHeight,
-        },
+        primitives::DaBlockHeight,
         SealedBlock,
     },
     fuel_types::BlockHeight,
@@ -119,11 +116,11 @@ impl ImporterDatabase for Database {
 impl ExecutorDatabase for Database {
     fn seal_block(
         &mut self,
-        block_id: &BlockId,
+        height: &BlockHeight,
         consensus: &Consensus,
     ) -> StorageResult<Option<Consensus>> {
         self.storage::<SealedBlockConsensus>()
-            .insert(block_id, consensus)
+            .insert(height, consensus)
             .map_err(Into::into)
     }
 }
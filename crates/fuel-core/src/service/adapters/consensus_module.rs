@@ -8,6 +8,7 @@ use crate::{
         Config,
     },
 };
+use fuel_core_chain_config::LastBlockConfig;
 use fuel_core_consensus_module::block_verifier::{
     config::Config as VerifierConfig,
     Verifier,
@@ -30,11 +31,13 @@ pub mod poa;
 impl VerifierAdapter {
     pub fn new(
         config: &Config,
+        last_block: Option<LastBlockConfig>,
         database: Database,
         relayer: MaybeRelayerAdapter,
     ) -> Self {
         let config = VerifierConfig::new(
             config.chain_conf.clone(),
+            last_block,
             config.manual_blocks_enabled,
             config.verifier.clone(),
         );
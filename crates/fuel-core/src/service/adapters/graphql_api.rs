@@ -5,6 +5,8 @@ use crate::{
     },
     fuel_core_graphql_api::ports::{
         BlockProducerPort,
+        DatabaseArchive,
+        DatabaseCheckpoint,
         DatabaseBlocks,
         DatabaseChain,
         DatabaseCoins,
@@ -41,14 +43,21 @@ use fuel_core_txpool::{
     },
 };
 use fuel_core_types::{
-    blockchain::primitives::{
-        BlockId,
-        DaBlockHeight,
+    blockchain::{
+        block::CompressedBlock,
+        primitives::{
+            BlockId,
+            DaBlockHeight,
+        },
     },
-    entities::message::{
-        MerkleProof,
-        Message,
+    entities::{
+        coins::coin::CompressedCoin,
+        message::{
+            MerkleProof,
+            Message,
+        },
     },
+    fuel_asm::Word,
     fuel_tx::{
         Address,
         AssetId,
@@ -59,6 +68,7 @@ use fuel_core_types::{
     },
     fuel_types::{
         BlockHeight,
+        Bytes32,
         Nonce,
     },
     services::{
@@ -72,6 +82,7 @@ use fuel_core_types::{
 };
 use std::{
     ops::Deref,
+    path::Path,
     sync::Arc,
 };
 
@@ -81,12 +92,17 @@ impl DatabaseBlocks for Database {
             .and_then(|height| height.ok_or(not_found!("BlockId")))
     }
 
-    fn blocks_ids(
+    fn block_height(&self, id: &BlockId) -> StorageResult<BlockHeight> {
+        self.get_block_height(id)
+            .and_then(|height| height.ok_or(not_found!("BlockHeight")))
+    }
+
+    fn blocks(
         &self,
         start: Option<BlockHeight>,
         direction: IterDirection,
-    ) -> BoxedIter<'_, StorageResult<(BlockHeight, BlockId)>> {
-        self.all_block_ids(start, direction)
+    ) -> BoxedIter<'_, StorageResult<(BlockHeight, CompressedBlock)>> {
+        self.all_blocks(start, direction)
             .map(|result| result.map_err(StorageError::from))
             .into_boxed()
     }
@@ -97,6 +113,10 @@ impl DatabaseBlocks for Database {
             .transpose()
             .ok_or(not_found!("BlockId"))??)
     }
+
+    fn pruned_height(&self) -> StorageResult<Option<BlockHeight>> {
+        Ok(self.pruned_height()?)
+    }
 }
 
 impl DatabaseTransactions for Database {
@@ -180,6 +200,48 @@ impl DatabaseContracts for Database {
     }
 }
 
+impl DatabaseArchive for Database {
+    fn archived_contract_balance(
+        &self,
+        contract: &ContractId,
+        asset: &AssetId,
+        height: BlockHeight,
+    ) -> StorageResult<Option<Word>> {
+        self.contract_balance_at(contract, asset, height)
+    }
+
+    fn archived_contract_state(
+        &self,
+        contract: &ContractId,
+        key: &Bytes32,
+        height: BlockHeight,
+    ) -> StorageResult<Option<Bytes32>> {
+        self.contract_state_at(contract, key, height)
+    }
+
+    fn archived_owned_coins(
+        &self,
+        owner: &Address,
+        height: BlockHeight,
+    ) -> StorageResult<Vec<CompressedCoin>> {
+        self.owned_coins_at(owner, height)
+    }
+
+    fn archived_owned_messages(
+        &self,
+        owner: &Address,
+        height: BlockHeight,
+    ) -> StorageResult<Vec<Message>> {
+        self.owned_messages_at(owner, height)
+    }
+}
+
+impl DatabaseCheckpoint for Database {
+    fn checkpoint(&self, path: &Path) -> StorageResult<()> {
+        Ok(self.backup(path)?)
+    }
+}
+
 impl DatabaseChain for Database {
     fn chain_name(&self) -> StorageResult<String> {
         pub const DEFAULT_NAME: &str = "Fuel.testnet";
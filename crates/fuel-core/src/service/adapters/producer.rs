@@ -111,9 +111,8 @@ impl fuel_core_producer::ports::Relayer for MaybeRelayerAdapter {
 
 impl fuel_core_producer::ports::BlockProducerDatabase for Database {
     fn get_block(&self, height: &BlockHeight) -> StorageResult<Cow<CompressedBlock>> {
-        let id = self.get_block_id(height)?.ok_or(not_found!("BlockId"))?;
         self.storage::<FuelBlocks>()
-            .get(&id)?
+            .get(height)?
             .ok_or(not_found!(FuelBlocks))
     }
 
@@ -1,3 +1,4 @@
+use crate::state::rocks_db_config::RocksDbConfig;
 use clap::ValueEnum;
 use fuel_core_chain_config::{
     default_consensus_dev_key,
@@ -35,7 +36,13 @@ pub struct Config {
     pub max_database_cache_size: usize,
     pub database_path: PathBuf,
     pub database_type: DbType,
+    /// The tuning of the RocksDB column families.
+    pub database_config: RocksDbConfig,
     pub chain_conf: ChainConfig,
+    /// The file with the genesis state in the streamed format, see
+    /// [`StateEntry`](fuel_core_chain_config::StateEntry). It replaces the initial
+    /// state of the `chain_conf`, so the state doesn't need to fit into memory.
+    pub genesis_state: Option<PathBuf>,
     // default to false until downstream consumers stabilize
     pub utxo_validation: bool,
     pub manual_blocks_enabled: bool,
@@ -45,6 +52,17 @@ pub struct Config {
     pub block_producer: fuel_core_producer::Config,
     pub block_executor: fuel_core_executor::Config,
     pub block_importer: fuel_core_importer::Config,
+    /// The pruning of the historical blocks data. All blocks are kept if it is `None`.
+    pub block_pruning: Option<crate::service::pruner::Config>,
+    /// Record the historical state to answer the queries at the past block heights.
+    pub archive: bool,
+    /// Serve only the queries from the read-only secondary instance of the database,
+    /// that follows the primary instance writing to the `database_path`. The block
+    /// production, the transaction pool and the networking are disabled.
+    pub read_only_secondary: Option<crate::service::secondary::Config>,
+    /// The directory for the database checkpoints created via the GraphQL API.
+    /// The checkpoints are disabled if it is `None`.
+    pub database_checkpoint_dir: Option<PathBuf>,
     #[cfg(feature = "relayer")]
     pub relayer: fuel_core_relayer::Config,
     #[cfg(feature = "p2p")]
@@ -74,7 +92,9 @@ impl Config {
             database_type: DbType::RocksDb,
             #[cfg(not(feature = "rocksdb"))]
             database_type: DbType::InMemory,
+            database_config: Default::default(),
             chain_conf: chain_conf.clone(),
+            genesis_state: None,
             manual_blocks_enabled: false,
             block_production: Trigger::Instant,
             vm: Default::default(),
@@ -89,6 +109,10 @@ impl Config {
             block_producer: Default::default(),
             block_executor: Default::default(),
             block_importer: Default::default(),
+            block_pruning: None,
+            archive: false,
+            read_only_secondary: None,
+            database_checkpoint_dir: None,
             #[cfg(feature = "relayer")]
             relayer: Default::default(),
             #[cfg(feature = "p2p")]
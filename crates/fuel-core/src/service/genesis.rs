@@ -2,26 +2,45 @@ use crate::{
     database::Database,
     service::config::Config,
 };
-use anyhow::anyhow;
+use anyhow::{
+    anyhow,
+    ensure,
+    Context,
+};
 use fuel_core_chain_config::{
+    CoinConfig,
+    ContractBalanceConfig,
     ContractConfig,
+    ContractStateConfig,
     GenesisCommitment,
-    StateConfig,
+    LastBlockConfig,
+    MessageConfig,
+    StateEntry,
+    StateReader,
 };
 use fuel_core_executor::refs::ContractRef;
 use fuel_core_importer::Importer;
 use fuel_core_storage::{
+    not_found,
     tables::{
         Coins,
+        ContractsAssets,
         ContractsInfo,
         ContractsLatestUtxo,
         ContractsRawCode,
+        ContractsState,
         FuelBlocks,
         Messages,
     },
-    transactional::Transactional,
+    transactional::{
+        Transaction as StorageTransactionTrait,
+        Transactional,
+    },
     MerkleRoot,
     StorageAsMut,
+    StorageAsRef,
+    StorageInspect,
+    StorageMutate,
 };
 use fuel_core_types::{
     blockchain::{
@@ -43,7 +62,11 @@ use fuel_core_types::{
         contract::ContractUtxoInfo,
         message::Message,
     },
-    fuel_merkle::binary,
+    fuel_merkle::binary::{
+        self,
+        in_memory::NodesTable,
+        Primitive,
+    },
     fuel_tx::{
         Contract,
         TxPointer,
@@ -51,6 +74,7 @@ use fuel_core_types::{
     },
     fuel_types::{
         bytes::WORD_SIZE,
+        BlockHeight,
         Bytes32,
         ContractId,
     },
@@ -60,6 +84,15 @@ use fuel_core_types::{
     },
 };
 use itertools::Itertools;
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    fs::File,
+    io::BufReader,
+};
+
+/// The number of the state entries committed to the database at once.
+const GENESIS_BATCH_SIZE: usize = 10_000;
 
 /// Loads state from the chain config into database
 pub fn maybe_initialize_state(
@@ -78,6 +111,21 @@ fn import_genesis_block(
     config: &Config,
     original_database: &Database,
 ) -> anyhow::Result<()> {
+    let roots = if let Some(path) = &config.genesis_state {
+        let file = File::open(path).with_context(|| {
+            format!("failed to open the genesis state {}", path.display())
+        })?;
+        import_state(original_database, StateReader::new(BufReader::new(file)))?
+    } else {
+        let entries = config
+            .chain_conf
+            .initial_state
+            .iter()
+            .flat_map(|state| state.entries())
+            .map(Ok);
+        import_state(original_database, entries)?
+    };
+
     // start a db transaction for bulk-writing
     let mut database_transaction = Transactional::transaction(original_database);
 
@@ -85,17 +133,12 @@ fn import_genesis_block(
     // Initialize the chain id and height.
 
     let chain_config_hash = config.chain_conf.root()?.into();
-    let coins_root = init_coin_state(database, &config.chain_conf.initial_state)?.into();
-    let contracts_root =
-        init_contracts(database, &config.chain_conf.initial_state)?.into();
-    let messages_root = init_da_messages(database, &config.chain_conf.initial_state)?;
-    let messages_root = messages_root.into();
 
     let genesis = Genesis {
         chain_config_hash,
-        coins_root,
-        contracts_root,
-        messages_root,
+        coins_root: roots.coins_root.into(),
+        contracts_root: roots.contracts_root.into(),
+        messages_root: roots.messages_root.into(),
     };
 
     let block = Block::new(
@@ -106,16 +149,12 @@ fn import_genesis_block(
                 generated: Empty,
             },
             consensus: ConsensusHeader::<Empty> {
-                // The genesis is a first block, so previous root is zero.
-                prev_root: Bytes32::zeroed(),
-                // The initial height is defined by the `ChainConfig`.
+                // The genesis is a first block, so previous root is zero, unless
+                // the chain continues the previous one after the regenesis.
+                prev_root: roots.prev_root,
+                // The initial height is defined by the genesis state.
                 // If it is `None` then it will be zero.
-                height: config
-                    .chain_conf
-                    .initial_state
-                    .as_ref()
-                    .map(|config| config.height.unwrap_or_else(|| 0u32.into()))
-                    .unwrap_or_else(|| 0u32.into()),
+                height: roots.height,
                 time: fuel_core_types::tai64::Tai64::UNIX_EPOCH,
                 generated: Empty,
             },
@@ -125,11 +164,12 @@ fn import_genesis_block(
         &[],
     );
 
-    let block_id = block.id();
     database.storage::<FuelBlocks>().insert(
-        &block_id,
+        block.header().height(),
         &block.compress(&config.chain_conf.transaction_parameters.chain_id),
     )?;
+    // The state is fully imported once the genesis block is committed.
+    database.clear_genesis_progress()?;
     let consensus = Consensus::Genesis(genesis);
     let block = SealedBlock {
         entity: block,
@@ -149,155 +189,441 @@ fn import_genesis_block(
     Ok(())
 }
 
-fn init_coin_state(
-    db: &mut Database,
-    state: &Option<StateConfig>,
-) -> anyhow::Result<MerkleRoot> {
-    let mut coins_tree = binary::in_memory::MerkleTree::new();
-    // TODO: Store merkle sum tree root over coins with unspecified utxo ids.
-    let mut generated_output_index: u64 = 0;
-    if let Some(state) = &state {
-        if let Some(coins) = &state.coins {
-            for coin in coins {
-                let utxo_id = UtxoId::new(
-                    // generated transaction id([0..[out_index/255]])
-                    coin.tx_id.unwrap_or_else(|| {
-                        Bytes32::try_from(
-                            (0..(Bytes32::LEN - WORD_SIZE))
-                                .map(|_| 0u8)
-                                .chain(
-                                    (generated_output_index / 255)
-                                        .to_be_bytes()
-                                        .into_iter(),
-                                )
-                                .collect_vec()
-                                .as_slice(),
-                        )
-                        .expect("Incorrect genesis transaction id byte length")
-                    }),
-                    coin.output_index.unwrap_or_else(|| {
-                        generated_output_index += 1;
-                        (generated_output_index % 255) as u8
-                    }),
-                );
-
-                let coin = CompressedCoin {
-                    owner: coin.owner,
-                    amount: coin.amount,
-                    asset_id: coin.asset_id,
-                    maturity: coin.maturity.unwrap_or_default(),
-                    tx_pointer: TxPointer::new(
-                        coin.tx_pointer_block_height.unwrap_or_default(),
-                        coin.tx_pointer_tx_idx.unwrap_or_default(),
-                    ),
-                };
-
-                // ensure coin can't point to blocks in the future
-                if coin.tx_pointer.block_height() > state.height.unwrap_or_default() {
-                    return Err(anyhow!(
-                        "coin tx_pointer height cannot be greater than genesis block"
-                    ))
-                }
+/// Returns the last block of the previous chain if the genesis state starts with it.
+pub(crate) fn last_block(config: &Config) -> anyhow::Result<Option<LastBlockConfig>> {
+    let path = match &config.genesis_state {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    let file = File::open(path).with_context(|| {
+        format!("failed to open the genesis state {}", path.display())
+    })?;
+    match StateReader::new(BufReader::new(file)).next().transpose()? {
+        Some(StateEntry::LastBlock(last_block)) => Ok(Some(last_block)),
+        _ => Ok(None),
+    }
+}
 
-                if db.storage::<Coins>().insert(&utxo_id, &coin)?.is_some() {
-                    return Err(anyhow!("Coin should not exist"))
-                }
-                coins_tree.push(coin.root()?.as_slice())
-            }
+/// Verifies that the chain in the `database` was started by the regenesis from
+/// the latest state of the `source` database: its genesis block follows the last
+/// block of the source chain and commits to the same state.
+///
+/// The roots of the source state are calculated from its tables, independently
+/// of the exported snapshot.
+pub fn verify_regenesis(source: &Database, database: &Database) -> anyhow::Result<()> {
+    let expected = source_roots(source)?;
+
+    let genesis_height = database.genesis_height()?;
+    let genesis = database.get_genesis()?;
+    let header = database
+        .storage::<FuelBlocks>()
+        .get(&genesis_height)?
+        .ok_or(not_found!(FuelBlocks))?
+        .header()
+        .clone();
+
+    ensure!(
+        genesis_height == expected.height,
+        "the genesis height {} doesn't follow the last block of the source chain, \
+        expected {}",
+        genesis_height,
+        expected.height
+    );
+    ensure!(
+        header.prev_root() == &expected.prev_root,
+        "the previous root of the genesis {} doesn't match the root of the source \
+        blocks {}",
+        header.prev_root(),
+        expected.prev_root
+    );
+    for (name, actual, expected) in [
+        ("coins", genesis.coins_root, expected.coins_root),
+        ("contracts", genesis.contracts_root, expected.contracts_root),
+        ("messages", genesis.messages_root, expected.messages_root),
+    ] {
+        ensure!(
+            actual == expected.into(),
+            "the {name} root of the genesis {actual} doesn't match the source state {}",
+            Bytes32::from(expected)
+        );
+    }
+    Ok(())
+}
+
+/// Calculates the roots of the genesis of the chain continuing the `source` one,
+/// in the same order as the state is imported from its snapshot.
+fn source_roots(source: &Database) -> anyhow::Result<GenesisRoots> {
+    let last_block = source.last_block()?;
+
+    let mut coins_tree = MerkleRootCalculator::default();
+    for coin in source.snapshot_coins() {
+        coins_tree.push(coin?.root()?.as_slice());
+    }
+
+    let mut contracts_tree = MerkleRootCalculator::default();
+    let mut contracts_db = source.clone();
+    for contract_id in source.snapshot_contract_ids() {
+        let contract = ContractRef::new(&mut contracts_db, contract_id?);
+        contracts_tree.push(contract.root()?.as_slice());
+    }
+
+    let mut messages_tree = MerkleRootCalculator::default();
+    for message in source.snapshot_messages() {
+        messages_tree.push(message?.root()?.as_slice());
+    }
+
+    Ok(GenesisRoots {
+        height: last_block.genesis_height()?,
+        prev_root: last_block.blocks_root,
+        coins_root: coins_tree.root(),
+        contracts_root: contracts_tree.root(),
+        messages_root: messages_tree.root(),
+    })
+}
+
+/// The height and the Merkle roots of the imported genesis state.
+#[derive(Debug, PartialEq, Eq)]
+struct GenesisRoots {
+    height: BlockHeight,
+    /// The root of the blocks of the previous chain, or zero.
+    prev_root: Bytes32,
+    coins_root: MerkleRoot,
+    contracts_root: MerkleRoot,
+    messages_root: MerkleRoot,
+}
+
+/// Imports the state `entries` into the `database`, committing them in batches of
+/// [`GENESIS_BATCH_SIZE`] entries along with the number of imported entries.
+///
+/// If the previous import was interrupted, the already committed entries are not
+/// written again, but they are still read to calculate the Merkle roots of the state.
+fn import_state<I>(database: &Database, entries: I) -> anyhow::Result<GenesisRoots>
+where
+    I: Iterator<Item = anyhow::Result<StateEntry>>,
+{
+    let imported = database.genesis_progress()?.unwrap_or_default();
+    let mut importer = StateImporter::default();
+    let mut transaction = database.transaction();
+    let mut pending = 0;
+    let mut total = 0;
+
+    for (index, entry) in entries.enumerate() {
+        let index = index as u64;
+        total = index + 1;
+        let entry = entry.with_context(|| format!("invalid state entry {index}"))?;
+        let skip = index < imported;
+        importer.import(transaction.as_mut(), entry, skip)?;
+
+        if !skip {
+            pending += 1;
         }
+        if pending == GENESIS_BATCH_SIZE {
+            transaction.as_mut().set_genesis_progress(total)?;
+            transaction.commit()?;
+            transaction = database.transaction();
+            pending = 0;
+        }
+    }
+
+    let roots = importer.finish(transaction.as_mut())?;
+    if pending > 0 {
+        transaction.as_mut().set_genesis_progress(total)?;
     }
-    Ok(coins_tree.root())
+    transaction.commit()?;
+    Ok(roots)
 }
 
-fn init_contracts(
-    db: &mut Database,
-    state: &Option<StateConfig>,
-) -> anyhow::Result<MerkleRoot> {
-    let mut contracts_tree = binary::in_memory::MerkleTree::new();
-    // initialize contract state
-    if let Some(state) = &state {
-        if let Some(contracts) = &state.contracts {
-            for (generated_output_index, contract_config) in contracts.iter().enumerate()
-            {
-                let contract = Contract::from(contract_config.code.as_slice());
-                let salt = contract_config.salt;
-                let root = contract.root();
-                let contract_id = contract_config.contract_id;
-                let utxo_id = if let (Some(tx_id), Some(output_idx)) =
-                    (contract_config.tx_id, contract_config.output_index)
-                {
-                    UtxoId::new(tx_id, output_idx)
-                } else {
-                    UtxoId::new(
-                        // generated transaction id([0..[out_index/255]])
-                        Bytes32::try_from(
-                            (0..(Bytes32::LEN - WORD_SIZE))
-                                .map(|_| 0u8)
-                                .chain(
-                                    (generated_output_index as u64 / 255)
-                                        .to_be_bytes()
-                                        .into_iter(),
-                                )
-                                .collect_vec()
-                                .as_slice(),
-                        )
-                        .expect("Incorrect genesis transaction id byte length"),
-                        generated_output_index as u8,
-                    )
-                };
-                let tx_pointer = if let (Some(block_height), Some(tx_idx)) = (
-                    contract_config.tx_pointer_block_height,
-                    contract_config.tx_pointer_tx_idx,
-                ) {
-                    TxPointer::new(block_height, tx_idx)
-                } else {
-                    TxPointer::default()
-                };
-
-                if tx_pointer.block_height() > state.height.unwrap_or_default() {
-                    return Err(anyhow!(
-                        "contract tx_pointer cannot be greater than genesis block"
-                    ))
-                }
+/// Writes the state entries into the database one by one and accumulates the
+/// Merkle roots of the state.
+#[derive(Default)]
+struct StateImporter {
+    height: Option<BlockHeight>,
+    last_block: Option<LastBlockConfig>,
+    /// Any entry was already imported, so the height can't be set anymore.
+    started: bool,
+    coins_tree: MerkleRootCalculator,
+    contracts_tree: MerkleRootCalculator,
+    messages_tree: MerkleRootCalculator,
+    // TODO: Store merkle sum tree root over coins with unspecified utxo ids.
+    coins_generated_output_index: u64,
+    contracts_count: u64,
+    /// The last imported contract. Its state and balances may follow it as
+    /// separate entries, so its root is calculated with the next contract.
+    contract: Option<ContractId>,
+}
 
-                // insert contract code
-                if db
-                    .storage::<ContractsRawCode>()
-                    .insert(&contract_id, contract.as_ref())?
-                    .is_some()
-                {
-                    return Err(anyhow!("Contract code should not exist"))
-                }
+impl StateImporter {
+    /// Imports the `entry` into the `db`. The `skip`ped entry was already written
+    /// by the interrupted import and only updates the roots.
+    fn import(
+        &mut self,
+        db: &mut Database,
+        entry: StateEntry,
+        skip: bool,
+    ) -> anyhow::Result<()> {
+        if !matches!(
+            entry,
+            StateEntry::ContractState(_) | StateEntry::ContractBalance(_)
+        ) {
+            self.finish_contract(db)?;
+        }
 
-                // insert contract root
-                if db
-                    .storage::<ContractsInfo>()
-                    .insert(&contract_id, &(salt, root))?
-                    .is_some()
-                {
-                    return Err(anyhow!("Contract info should not exist"))
+        match entry {
+            StateEntry::Height(height) => {
+                if self.started {
+                    return Err(anyhow!("the height must be the first entry of the state"))
                 }
-                if db
-                    .storage::<ContractsLatestUtxo>()
-                    .insert(
-                        &contract_id,
-                        &ContractUtxoInfo {
-                            utxo_id,
-                            tx_pointer,
-                        },
-                    )?
-                    .is_some()
-                {
-                    return Err(anyhow!("Contract utxo should not exist"))
+                self.height = Some(height);
+            }
+            StateEntry::LastBlock(last_block) => {
+                if self.started {
+                    return Err(anyhow!(
+                        "the last block must be the first entry of the state"
+                    ))
                 }
-                init_contract_state(db, &contract_id, contract_config)?;
-                init_contract_balance(db, &contract_id, contract_config)?;
-                contracts_tree
-                    .push(ContractRef::new(&mut *db, contract_id).root()?.as_slice());
+                self.height = Some(last_block.genesis_height()?);
+                self.last_block = Some(last_block);
+            }
+            StateEntry::Coin(coin) => self.import_coin(db, &coin, skip)?,
+            StateEntry::Contract(contract) => {
+                self.import_contract(db, &contract, skip)?
+            }
+            StateEntry::ContractState(state) => {
+                self.import_contract_state(db, &state, skip)?
             }
+            StateEntry::ContractBalance(balance) => {
+                self.import_contract_balance(db, &balance, skip)?
+            }
+            StateEntry::Message(message) => self.import_message(db, &message, skip)?,
+        }
+        self.started = true;
+        Ok(())
+    }
+
+    fn height(&self) -> BlockHeight {
+        self.height.unwrap_or_default()
+    }
+
+    fn import_coin(
+        &mut self,
+        db: &mut Database,
+        coin: &CoinConfig,
+        skip: bool,
+    ) -> anyhow::Result<()> {
+        let generated_output_index = &mut self.coins_generated_output_index;
+        let utxo_id = UtxoId::new(
+            // generated transaction id([0..[out_index/255]])
+            coin.tx_id
+                .unwrap_or_else(|| generated_tx_id(*generated_output_index)),
+            coin.output_index.unwrap_or_else(|| {
+                *generated_output_index += 1;
+                (*generated_output_index % 255) as u8
+            }),
+        );
+
+        let coin = CompressedCoin {
+            owner: coin.owner,
+            amount: coin.amount,
+            asset_id: coin.asset_id,
+            maturity: coin.maturity.unwrap_or_default(),
+            tx_pointer: TxPointer::new(
+                coin.tx_pointer_block_height.unwrap_or_default(),
+                coin.tx_pointer_tx_idx.unwrap_or_default(),
+            ),
+        };
+
+        // ensure coin can't point to blocks in the future
+        if coin.tx_pointer.block_height() > self.height() {
+            return Err(anyhow!(
+                "coin tx_pointer height cannot be greater than genesis block"
+            ))
         }
+
+        if !skip && db.storage::<Coins>().insert(&utxo_id, &coin)?.is_some() {
+            return Err(anyhow!("Coin should not exist"))
+        }
+        self.coins_tree.push(coin.root()?.as_slice());
+        Ok(())
+    }
+
+    fn import_contract(
+        &mut self,
+        db: &mut Database,
+        contract_config: &ContractConfig,
+        skip: bool,
+    ) -> anyhow::Result<()> {
+        let generated_output_index = self.contracts_count;
+        self.contracts_count += 1;
+
+        let contract = Contract::from(contract_config.code.as_slice());
+        let salt = contract_config.salt;
+        let root = contract.root();
+        let contract_id = contract_config.contract_id;
+        let utxo_id = if let (Some(tx_id), Some(output_idx)) =
+            (contract_config.tx_id, contract_config.output_index)
+        {
+            UtxoId::new(tx_id, output_idx)
+        } else {
+            UtxoId::new(
+                // generated transaction id([0..[out_index/255]])
+                generated_tx_id(generated_output_index),
+                generated_output_index as u8,
+            )
+        };
+        let tx_pointer = if let (Some(block_height), Some(tx_idx)) = (
+            contract_config.tx_pointer_block_height,
+            contract_config.tx_pointer_tx_idx,
+        ) {
+            TxPointer::new(block_height, tx_idx)
+        } else {
+            TxPointer::default()
+        };
+
+        if tx_pointer.block_height() > self.height() {
+            return Err(anyhow!(
+                "contract tx_pointer cannot be greater than genesis block"
+            ))
+        }
+        self.contract = Some(contract_id);
+        if skip {
+            return Ok(())
+        }
+
+        // insert contract code
+        if db
+            .storage::<ContractsRawCode>()
+            .insert(&contract_id, contract.as_ref())?
+            .is_some()
+        {
+            return Err(anyhow!("Contract code should not exist"))
+        }
+
+        // insert contract root
+        if db
+            .storage::<ContractsInfo>()
+            .insert(&contract_id, &(salt, root))?
+            .is_some()
+        {
+            return Err(anyhow!("Contract info should not exist"))
+        }
+        if db
+            .storage::<ContractsLatestUtxo>()
+            .insert(
+                &contract_id,
+                &ContractUtxoInfo {
+                    utxo_id,
+                    tx_pointer,
+                },
+            )?
+            .is_some()
+        {
+            return Err(anyhow!("Contract utxo should not exist"))
+        }
+        init_contract_state(db, &contract_id, contract_config)?;
+        init_contract_balance(db, &contract_id, contract_config)?;
+        Ok(())
+    }
+
+    fn import_contract_state(
+        &mut self,
+        db: &mut Database,
+        state: &ContractStateConfig,
+        skip: bool,
+    ) -> anyhow::Result<()> {
+        self.ensure_follows_contract(&state.contract_id)?;
+        if !skip {
+            db.storage::<ContractsState>()
+                .insert(&(&state.contract_id, &state.key).into(), &state.value)?;
+        }
+        Ok(())
+    }
+
+    fn import_contract_balance(
+        &mut self,
+        db: &mut Database,
+        balance: &ContractBalanceConfig,
+        skip: bool,
+    ) -> anyhow::Result<()> {
+        self.ensure_follows_contract(&balance.contract_id)?;
+        if !skip {
+            db.storage::<ContractsAssets>().insert(
+                &(&balance.contract_id, &balance.asset_id).into(),
+                &balance.amount,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn ensure_follows_contract(&self, contract_id: &ContractId) -> anyhow::Result<()> {
+        if self.contract != Some(*contract_id) {
+            return Err(anyhow!(
+                "the state of the contract {contract_id} must follow the contract"
+            ))
+        }
+        Ok(())
+    }
+
+    fn import_message(
+        &mut self,
+        db: &mut Database,
+        msg: &MessageConfig,
+        skip: bool,
+    ) -> anyhow::Result<()> {
+        let message = Message {
+            sender: msg.sender,
+            recipient: msg.recipient,
+            nonce: msg.nonce,
+            amount: msg.amount,
+            data: msg.data.clone(),
+            da_height: msg.da_height,
+        };
+
+        if !skip
+            && db
+                .storage::<Messages>()
+                .insert(message.id(), &message)?
+                .is_some()
+        {
+            return Err(anyhow!("Message should not exist"))
+        }
+        self.messages_tree.push(message.root()?.as_slice());
+        Ok(())
+    }
+
+    /// Adds the root of the last imported contract, including its state and balances.
+    fn finish_contract(&mut self, db: &mut Database) -> anyhow::Result<()> {
+        if let Some(contract_id) = self.contract.take() {
+            self.contracts_tree
+                .push(ContractRef::new(&mut *db, contract_id).root()?.as_slice());
+        }
+        Ok(())
+    }
+
+    fn finish(mut self, db: &mut Database) -> anyhow::Result<GenesisRoots> {
+        self.finish_contract(db)?;
+        Ok(GenesisRoots {
+            height: self.height(),
+            prev_root: self
+                .last_block
+                .map(|last_block| last_block.blocks_root)
+                .unwrap_or_else(Bytes32::zeroed),
+            coins_root: self.coins_tree.root(),
+            contracts_root: self.contracts_tree.root(),
+            messages_root: self.messages_tree.root(),
+        })
     }
-    Ok(contracts_tree.root())
+}
+
+/// The generated transaction id `[0..[out_index/255]]` of the genesis UTXO.
+fn generated_tx_id(generated_output_index: u64) -> Bytes32 {
+    Bytes32::try_from(
+        (0..(Bytes32::LEN - WORD_SIZE))
+            .map(|_| 0u8)
+            .chain((generated_output_index / 255).to_be_bytes().into_iter())
+            .collect_vec()
+            .as_slice(),
+    )
+    .expect("Incorrect genesis transaction id byte length")
 }
 
 fn init_contract_state(
@@ -312,38 +638,6 @@ fn init_contract_state(
     Ok(())
 }
 
-fn init_da_messages(
-    db: &mut Database,
-    state: &Option<StateConfig>,
-) -> anyhow::Result<MerkleRoot> {
-    let mut message_tree = binary::in_memory::MerkleTree::new();
-    if let Some(state) = &state {
-        if let Some(message_state) = &state.messages {
-            for msg in message_state {
-                let message = Message {
-                    sender: msg.sender,
-                    recipient: msg.recipient,
-                    nonce: msg.nonce,
-                    amount: msg.amount,
-                    data: msg.data.clone(),
-                    da_height: msg.da_height,
-                };
-
-                if db
-                    .storage::<Messages>()
-                    .insert(message.id(), &message)?
-                    .is_some()
-                {
-                    return Err(anyhow!("Message should not exist"))
-                }
-                message_tree.push(message.root()?.as_slice());
-            }
-        }
-    }
-
-    Ok(message_tree.root())
-}
-
 fn init_contract_balance(
     db: &mut Database,
     contract_id: &ContractId,
@@ -356,6 +650,62 @@ fn init_contract_balance(
     Ok(())
 }
 
+/// The storage of the [`MerkleRootCalculator`] that drops all nodes.
+#[derive(Default)]
+struct NoNodes;
+
+impl StorageInspect<NodesTable> for NoNodes {
+    type Error = Infallible;
+
+    fn get(&self, _: &u64) -> Result<Option<Cow<Primitive>>, Self::Error> {
+        Ok(None)
+    }
+
+    fn contains_key(&self, _: &u64) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+}
+
+impl StorageMutate<NodesTable> for NoNodes {
+    fn insert(
+        &mut self,
+        _: &u64,
+        _: &Primitive,
+    ) -> Result<Option<Primitive>, Self::Error> {
+        Ok(None)
+    }
+
+    fn remove(&mut self, _: &u64) -> Result<Option<Primitive>, Self::Error> {
+        Ok(None)
+    }
+}
+
+/// Calculates the root of the binary Merkle tree. Only the peaks of the tree are
+/// kept in memory, so the memory usage is logarithmic in the number of leaves.
+struct MerkleRootCalculator {
+    tree: binary::MerkleTree<NodesTable, NoNodes>,
+}
+
+impl Default for MerkleRootCalculator {
+    fn default() -> Self {
+        Self {
+            tree: binary::MerkleTree::new(NoNodes),
+        }
+    }
+}
+
+impl MerkleRootCalculator {
+    fn push(&mut self, data: &[u8]) {
+        self.tree
+            .push(data)
+            .unwrap_or_else(|never: Infallible| match never {})
+    }
+
+    fn root(&self) -> MerkleRoot {
+        self.tree.root()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -366,16 +716,10 @@ mod tests {
     };
     use fuel_core_chain_config::{
         ChainConfig,
-        CoinConfig,
-        MessageConfig,
-    };
-    use fuel_core_storage::{
-        tables::{
-            ContractsAssets,
-            ContractsState,
-        },
-        StorageAsRef,
+        ChainConfigDb,
+        StateConfig,
     };
+    use fuel_core_storage::StorageAsRef;
     use fuel_core_types::{
         blockchain::primitives::DaBlockHeight,
         entities::coins::coin::Coin,
@@ -383,7 +727,6 @@ mod tests {
         fuel_types::{
             Address,
             AssetId,
-            BlockHeight,
             Salt,
         },
     };
@@ -393,7 +736,10 @@ mod tests {
         RngCore,
         SeedableRng,
     };
-    use std::vec;
+    use std::{
+        iter,
+        vec,
+    };
 
     #[tokio::test]
     async fn config_initializes_chain_name() {
@@ -743,6 +1089,161 @@ mod tests {
         assert!(init_result.is_err())
     }
 
+    fn large_state(rng: &mut StdRng, coins: usize) -> StateConfig {
+        let contract = Contract::from(op::ret(0x10).to_bytes().to_vec());
+        let salt: Salt = rng.gen();
+        let root = contract.root();
+        let contract_id = contract.id(&salt, &root, &Contract::default_state_root());
+
+        StateConfig {
+            height: Some(BlockHeight::from(10u32)),
+            coins: Some(
+                (0..coins)
+                    .map(|_| CoinConfig {
+                        tx_id: None,
+                        output_index: None,
+                        tx_pointer_block_height: None,
+                        tx_pointer_tx_idx: None,
+                        maturity: None,
+                        owner: rng.gen(),
+                        amount: rng.gen(),
+                        asset_id: rng.gen(),
+                    })
+                    .collect(),
+            ),
+            contracts: Some(vec![ContractConfig {
+                contract_id,
+                code: contract.into(),
+                salt,
+                state: Some(vec![(rng.gen(), rng.gen()), (rng.gen(), rng.gen())]),
+                balances: Some(vec![(rng.gen(), rng.gen()), (rng.gen(), rng.gen())]),
+                tx_id: None,
+                output_index: None,
+                tx_pointer_block_height: None,
+                tx_pointer_tx_idx: None,
+            }]),
+            messages: Some(vec![MessageConfig {
+                sender: rng.gen(),
+                recipient: rng.gen(),
+                nonce: rng.gen(),
+                amount: rng.gen(),
+                data: vec![rng.gen()],
+                da_height: DaBlockHeight(0),
+            }]),
+        }
+    }
+
+    fn state_of(db: &Database) -> Vec<StateEntry> {
+        // The height is known only after the genesis block is committed.
+        db.state_entries().skip(1).map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn separate_contract_state_entries_match_the_embedded_state() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let state = large_state(&mut rng, 3);
+        let embedded = Database::default();
+        let embedded_roots = import_state(&embedded, state.entries().map(Ok)).unwrap();
+
+        let separate = Database::default();
+        let entries = state.entries().flat_map(|entry| match entry {
+            StateEntry::Contract(mut contract) => {
+                let contract_id = contract.contract_id;
+                let state = contract.state.take().unwrap_or_default();
+                let balances = contract.balances.take().unwrap_or_default();
+                iter::once(StateEntry::Contract(contract))
+                    .chain(state.into_iter().map(|(key, value)| {
+                        StateEntry::ContractState(ContractStateConfig {
+                            contract_id,
+                            key,
+                            value,
+                        })
+                    }))
+                    .chain(balances.into_iter().map(|(asset_id, amount)| {
+                        StateEntry::ContractBalance(ContractBalanceConfig {
+                            contract_id,
+                            asset_id,
+                            amount,
+                        })
+                    }))
+                    .collect_vec()
+            }
+            entry => vec![entry],
+        });
+        let separate_roots = import_state(&separate, entries.map(Ok)).unwrap();
+
+        assert_eq!(separate_roots, embedded_roots);
+        assert_eq!(state_of(&separate), state_of(&embedded));
+    }
+
+    #[test]
+    fn interrupted_import_continues_from_the_last_batch() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let state = large_state(&mut rng, GENESIS_BATCH_SIZE + 100);
+        let expected = Database::default();
+        let expected_roots = import_state(&expected, state.entries().map(Ok)).unwrap();
+
+        // the import fails in the middle of the second batch
+        let db = Database::default();
+        let failing = state
+            .entries()
+            .map(Ok)
+            .take(GENESIS_BATCH_SIZE + 50)
+            .chain(iter::once(Err(anyhow!("the file is truncated"))));
+        import_state(&db, failing).unwrap_err();
+        assert_eq!(
+            db.genesis_progress().unwrap(),
+            Some(GENESIS_BATCH_SIZE as u64)
+        );
+
+        let roots = import_state(&db, state.entries().map(Ok)).unwrap();
+        assert_eq!(roots, expected_roots);
+        assert_eq!(state_of(&db), state_of(&expected));
+    }
+
+    #[test]
+    fn height_must_be_the_first_entry() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let state = large_state(&mut rng, 1);
+        let entries = state
+            .entries()
+            .skip(1)
+            .chain(iter::once(StateEntry::Height(BlockHeight::from(10u32))));
+
+        let result = import_state(&Database::default(), entries.map(Ok));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn contract_state_must_follow_the_contract() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let entries = vec![StateEntry::ContractState(ContractStateConfig {
+            contract_id: rng.gen(),
+            key: rng.gen(),
+            value: rng.gen(),
+        })];
+
+        let result = import_state(&Database::default(), entries.into_iter().map(Ok));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn merkle_root_calculator_matches_in_memory_tree() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let mut calculator = MerkleRootCalculator::default();
+        let mut tree = binary::in_memory::MerkleTree::new();
+        assert_eq!(calculator.root(), tree.root());
+
+        for _ in 0..100 {
+            let leaf: Bytes32 = rng.gen();
+            calculator.push(leaf.as_slice());
+            tree.push(leaf.as_slice());
+            assert_eq!(calculator.root(), tree.root());
+        }
+    }
+
     fn get_coins(db: &Database, owner: &Address) -> Vec<Coin> {
         db.owned_coins_ids(owner, None, None)
             .map(|r| {
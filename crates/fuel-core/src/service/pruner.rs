@@ -0,0 +1,134 @@
+//! The service removes the historical data of the blocks that are outside
+//! of the retention window.
+
+use crate::database::Database;
+use fuel_core_services::{
+    stream::BoxStream,
+    EmptyShared,
+    RunnableService,
+    RunnableTask,
+    ServiceRunner,
+    StateWatcher,
+};
+use fuel_core_types::{
+    fuel_types::BlockHeight,
+    services::block_importer::ImportResult,
+};
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+
+pub type Service = ServiceRunner<Task>;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// The number of the latest blocks whose transactions, receipts and statuses are kept.
+    pub retained_blocks: u32,
+    /// The maximum number of blocks pruned per imported block. It limits the time
+    /// spent on pruning a long history when the pruning is enabled on the existing node.
+    pub batch_size: u32,
+}
+
+impl Config {
+    pub fn new(retained_blocks: u32) -> Self {
+        Self {
+            retained_blocks,
+            batch_size: 100,
+        }
+    }
+}
+
+pub struct Task {
+    config: Config,
+    database: Database,
+    block_stream: BoxStream<Arc<ImportResult>>,
+}
+
+impl Task {
+    /// Prunes the blocks that are older than the `retained_blocks` relative to the
+    /// `latest_height`. The pruning is done in the blocking thread because it performs
+    /// a lot of database operations.
+    async fn prune(&self, latest_height: BlockHeight) -> anyhow::Result<()> {
+        let Some(height) = latest_height.checked_sub(self.config.retained_blocks) else {
+            return Ok(())
+        };
+
+        let database = self.database.clone();
+        let batch_size = self.config.batch_size;
+        let pruned = tokio::task::spawn_blocking(move || {
+            database.prune_blocks(height.into(), batch_size)
+        })
+        .await??;
+
+        if pruned > 0 {
+            tracing::debug!("Pruned {} blocks up to the height {}", pruned, height);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl RunnableService for Task {
+    const NAME: &'static str = "Pruner";
+
+    type SharedData = EmptyShared;
+    type Task = Task;
+    type TaskParams = ();
+
+    fn shared_data(&self) -> Self::SharedData {
+        EmptyShared
+    }
+
+    async fn into_task(
+        self,
+        _: &StateWatcher,
+        _: Self::TaskParams,
+    ) -> anyhow::Result<Self::Task> {
+        let latest_height = self.database.latest_height()?;
+        self.prune(latest_height).await?;
+        Ok(self)
+    }
+}
+
+#[async_trait::async_trait]
+impl RunnableTask for Task {
+    async fn run(&mut self, watcher: &mut StateWatcher) -> anyhow::Result<bool> {
+        let should_continue;
+
+        tokio::select! {
+            biased;
+
+            _ = watcher.while_started() => {
+                should_continue = false;
+            }
+
+            result = self.block_stream.next() => {
+                if let Some(result) = result {
+                    let height = *result.sealed_block.entity.header().height();
+                    self.prune(height).await?;
+                    should_continue = true;
+                } else {
+                    should_continue = false;
+                }
+            }
+        }
+        Ok(should_continue)
+    }
+
+    async fn shutdown(self) -> anyhow::Result<()> {
+        // Nothing to shut down because each pruned block is committed atomically.
+        // The pruning continues from the last pruned block after the restart.
+        Ok(())
+    }
+}
+
+pub fn new_service(
+    config: Config,
+    database: Database,
+    block_stream: BoxStream<Arc<ImportResult>>,
+) -> Service {
+    Service::new(Task {
+        config,
+        database,
+        block_stream,
+    })
+}
@@ -0,0 +1,105 @@
+//! The service keeps the read-only secondary instance of the database up to date
+//! with the primary instance that imports the blocks.
+
+use crate::database::Database;
+use fuel_core_services::{
+    EmptyShared,
+    RunnableService,
+    RunnableTask,
+    ServiceRunner,
+    StateWatcher,
+};
+use std::time::Duration;
+use tokio::time::{
+    Interval,
+    MissedTickBehavior,
+};
+
+pub type Service = ServiceRunner<Task>;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// How often the secondary instance applies the new writes of the primary instance.
+    pub catch_up_interval: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            catch_up_interval: Duration::from_millis(500),
+        }
+    }
+}
+
+pub struct Task {
+    database: Database,
+    interval: Interval,
+}
+
+impl Task {
+    /// The catch-up is done in the blocking thread because it replays the write-ahead
+    /// log of the primary instance.
+    async fn catch_up(&self) -> anyhow::Result<()> {
+        let database = self.database.clone();
+        tokio::task::spawn_blocking(move || database.catch_up_with_primary()).await??;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl RunnableService for Task {
+    const NAME: &'static str = "SecondaryCatchUp";
+
+    type SharedData = EmptyShared;
+    type Task = Task;
+    type TaskParams = ();
+
+    fn shared_data(&self) -> Self::SharedData {
+        EmptyShared
+    }
+
+    async fn into_task(
+        self,
+        _: &StateWatcher,
+        _: Self::TaskParams,
+    ) -> anyhow::Result<Self::Task> {
+        self.catch_up().await?;
+        Ok(self)
+    }
+}
+
+#[async_trait::async_trait]
+impl RunnableTask for Task {
+    async fn run(&mut self, watcher: &mut StateWatcher) -> anyhow::Result<bool> {
+        let should_continue;
+
+        tokio::select! {
+            biased;
+
+            _ = watcher.while_started() => {
+                should_continue = false;
+            }
+
+            _ = self.interval.tick() => {
+                // The failed catch-up is retried on the next tick, the queries are
+                // served from the previous state meanwhile.
+                if let Err(err) = self.catch_up().await {
+                    tracing::warn!("Failed to catch up with the primary database: {}", err);
+                }
+                should_continue = true;
+            }
+        }
+        Ok(should_continue)
+    }
+
+    async fn shutdown(self) -> anyhow::Result<()> {
+        // Nothing to shut down because the secondary instance doesn't write.
+        Ok(())
+    }
+}
+
+pub fn new_service(config: Config, database: Database) -> Service {
+    let mut interval = tokio::time::interval(config.catch_up_interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    Service::new(Task { database, interval })
+}
@@ -15,6 +15,9 @@ use crate::{
             TxPoolAdapter,
             VerifierAdapter,
         },
+        genesis,
+        pruner,
+        secondary,
         Config,
         SharedState,
         SubServices,
@@ -23,6 +26,10 @@ use crate::{
 use fuel_core_poa::Trigger;
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use tokio_stream::{
+    wrappers::BroadcastStream,
+    StreamExt,
+};
 
 pub type PoAService =
     fuel_core_poa::Service<TxPoolAdapter, BlockProducerAdapter, BlockImporterAdapter>;
@@ -71,11 +78,16 @@ pub fn init_sub_services(
             gas_costs: config.chain_conf.gas_costs.clone(),
             backtrace: config.vm.backtrace,
             utxo_validation_default: config.utxo_validation,
+            archive: config.archive,
         }),
     };
 
-    let verifier =
-        VerifierAdapter::new(config, database.clone(), relayer_adapter.clone());
+    let verifier = VerifierAdapter::new(
+        config,
+        genesis::last_block(config)?,
+        database.clone(),
+        relayer_adapter.clone(),
+    );
 
     let importer_adapter = BlockImporterAdapter::new(
         config.block_importer.clone(),
@@ -181,6 +193,8 @@ pub fn init_sub_services(
             transaction_parameters: config.chain_conf.transaction_parameters,
             gas_costs: config.chain_conf.gas_costs.clone(),
             consensus_key: config.consensus_key.clone(),
+            database_checkpoint_dir: config.database_checkpoint_dir.clone(),
+            read_only: config.read_only_secondary.is_some(),
         },
         schema,
         Box::new(database.clone()),
@@ -201,6 +215,16 @@ pub fn init_sub_services(
         config: config.clone(),
     };
 
+    if let Some(secondary) = config.read_only_secondary.clone() {
+        // The secondary instance serves only the queries, so the transaction pool
+        // is not started, and the other writing services are disabled by the config.
+        let services: SubServices = vec![
+            Box::new(graph_ql),
+            Box::new(secondary::new_service(secondary, database.clone())),
+        ];
+        return Ok((services, shared))
+    }
+
     #[allow(unused_mut)]
     // `FuelService` starts and shutdowns all sub-services in the `services` order
     let mut services: SubServices = vec![
@@ -226,5 +250,17 @@ pub fn init_sub_services(
         }
     }
 
+    if let Some(pruning) = config.block_pruning.clone() {
+        let block_stream = Box::pin(
+            BroadcastStream::new(shared.block_importer.block_importer.subscribe())
+                .filter_map(|result| result.ok()),
+        );
+        services.push(Box::new(pruner::new_service(
+            pruning,
+            database.clone(),
+            block_stream,
+        )));
+    }
+
     Ok((services, shared))
 }
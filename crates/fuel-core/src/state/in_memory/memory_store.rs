@@ -11,6 +11,7 @@ use crate::{
         KeyValueStore,
         TransactableStorage,
         Value,
+        WriteOperation,
     },
 };
 use fuel_core_storage::iter::{
@@ -80,7 +81,10 @@ impl MemoryStore {
                 }
             }
             (Some(prefix), Some(start)) => {
-                if direction == IterDirection::Forward {
+                // The `start` outside of the `prefix` returns nothing.
+                if !start.starts_with(prefix) {
+                    vec![]
+                } else if direction == IterDirection::Forward {
                     lock.range(start.to_vec()..)
                         .take_while(|(key, _)| key.starts_with(prefix))
                         .map(clone)
@@ -210,7 +214,37 @@ impl KeyValueStore for MemoryStore {
     }
 }
 
-impl BatchOperations for MemoryStore {}
+impl BatchOperations for MemoryStore {
+    fn batch_write(
+        &self,
+        entries: &mut dyn Iterator<Item = (Vec<u8>, Column, WriteOperation)>,
+    ) -> DatabaseResult<()> {
+        let mut batch = BTreeMap::<usize, Vec<(Vec<u8>, WriteOperation)>>::new();
+        for (key, column, op) in entries {
+            batch.entry(column.as_usize()).or_default().push((key, op));
+        }
+
+        // All affected columns are locked in the ascending order before applying
+        // any change, so nobody observes the partially applied batch.
+        let mut locks: Vec<_> = batch
+            .keys()
+            .map(|column| self.inner[*column].lock().expect("poisoned"))
+            .collect();
+        for (lock, ops) in locks.iter_mut().zip(batch.into_values()) {
+            for (key, op) in ops {
+                match op {
+                    WriteOperation::Insert(value) => {
+                        lock.insert(key, value);
+                    }
+                    WriteOperation::Remove => {
+                        lock.remove(&key);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
 
 impl TransactableStorage for MemoryStore {}
 
@@ -219,6 +253,8 @@ mod tests {
     use super::*;
     use std::sync::Arc;
 
+    fuel_core_database::storage_conformance_tests!(|| Arc::new(MemoryStore::default()));
+
     #[test]
     fn can_use_unit_value() {
         let key = vec![0x00];
@@ -300,4 +336,35 @@ mod tests {
 
         assert!(!db.exists(&key, Column::Metadata).unwrap());
     }
+
+    #[test]
+    fn batch_write_applies_all_operations() {
+        let db = MemoryStore::default();
+        db.put(&[1], Column::Coins, Arc::new(vec![1])).unwrap();
+
+        let ops = vec![
+            (
+                vec![0],
+                Column::Metadata,
+                WriteOperation::Insert(Arc::new(vec![2])),
+            ),
+            (vec![1], Column::Coins, WriteOperation::Remove),
+            (
+                vec![2],
+                Column::Coins,
+                WriteOperation::Insert(Arc::new(vec![3])),
+            ),
+        ];
+        db.batch_write(&mut ops.into_iter()).unwrap();
+
+        assert_eq!(
+            db.get(&[0], Column::Metadata).unwrap(),
+            Some(Arc::new(vec![2]))
+        );
+        assert_eq!(db.get(&[1], Column::Coins).unwrap(), None);
+        assert_eq!(
+            db.get(&[2], Column::Coins).unwrap(),
+            Some(Arc::new(vec![3]))
+        );
+    }
 }
@@ -25,9 +25,12 @@ use itertools::{
 };
 use std::{
     cmp::Ordering,
-    collections::HashMap,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+        HashMap,
+    },
     fmt::Debug,
-    ops::DerefMut,
     sync::{
         Arc,
         Mutex,
@@ -52,19 +55,49 @@ impl MemoryTransactionView {
         }
     }
 
+    /// Writes all changes to the data source in one batch. The changes are kept if the
+    /// data source fails to write them, so the commit can be retried.
     pub fn commit(&self) -> DatabaseResult<()> {
-        let mut iter = self
+        let mut locks: Vec<_> = self
             .changes
             .iter()
-            .zip(enum_iterator::all::<Column>())
-            .flat_map(|(column_map, column)| {
-                let mut map = column_map.lock().expect("poisoned lock");
-                let changes = core::mem::take(map.deref_mut());
+            .map(|column_map| column_map.lock().expect("poisoned lock"))
+            .collect();
+
+        let mut iter = locks.iter().zip(enum_iterator::all::<Column>()).flat_map(
+            |(changes, column)| {
+                changes
+                    .iter()
+                    .map(move |(key, op)| (key.clone(), column, op.clone()))
+            },
+        );
+        self.data_source.batch_write(&mut iter)?;
+
+        for changes in locks.iter_mut() {
+            changes.clear();
+        }
+        Ok(())
+    }
 
-                changes.into_iter().map(move |t| (t.0, column, t.1))
-            });
+    /// Returns the keys of the `column` changed by the transaction with their values
+    /// in the underlying data source.
+    pub fn previous_values(
+        &self,
+        column: Column,
+    ) -> DatabaseResult<Vec<(Vec<u8>, Option<Value>)>> {
+        let keys: Vec<_> = self.changes[column.as_usize()]
+            .lock()
+            .expect("poisoned lock")
+            .keys()
+            .cloned()
+            .collect();
 
-        self.data_source.batch_write(&mut iter)
+        keys.into_iter()
+            .map(|key| {
+                let value = self.data_source.get(&key, column)?;
+                Ok((key, value))
+            })
+            .collect()
     }
 }
 
@@ -282,14 +315,203 @@ impl KeyValueStore for MemoryTransactionView {
     }
 }
 
-impl BatchOperations for MemoryTransactionView {}
+impl BatchOperations for MemoryTransactionView {
+    fn batch_write(
+        &self,
+        entries: &mut dyn Iterator<Item = (Vec<u8>, Column, WriteOperation)>,
+    ) -> DatabaseResult<()> {
+        let entries: Vec<_> = entries.collect();
+        let columns: BTreeSet<_> = entries
+            .iter()
+            .map(|(_, column, _)| column.as_usize())
+            .collect();
+
+        // The changes of the affected columns are locked until the view layer and
+        // the changes are both updated, so readers never observe a part of the batch.
+        let mut locks: BTreeMap<_, _> = columns
+            .into_iter()
+            .map(|column| (column, self.changes[column].lock().expect("poisoned lock")))
+            .collect();
+        self.view_layer.batch_write(&mut entries.iter().cloned())?;
+        for (key, column, op) in entries {
+            locks
+                .get_mut(&column.as_usize())
+                .expect("the column is locked above")
+                .insert(key, op);
+        }
+        Ok(())
+    }
+}
 
 impl TransactableStorage for MemoryTransactionView {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Arc;
+    use crate::database::Error as DatabaseError;
+    use std::sync::{
+        atomic::{
+            AtomicBool,
+            Ordering as AtomicOrdering,
+        },
+        Arc,
+    };
+
+    fuel_core_database::storage_conformance_tests!(|| {
+        Arc::new(MemoryTransactionView::new(Arc::new(MemoryStore::default())))
+    });
+
+    /// The data source that rejects the batches while `fail` is set.
+    #[derive(Debug, Default)]
+    struct FailingStore {
+        store: MemoryStore,
+        fail: AtomicBool,
+    }
+
+    impl KeyValueStore for FailingStore {
+        fn put(
+            &self,
+            key: &[u8],
+            column: Column,
+            value: Value,
+        ) -> DatabaseResult<Option<Value>> {
+            self.store.put(key, column, value)
+        }
+
+        fn write(&self, key: &[u8], column: Column, buf: &[u8]) -> DatabaseResult<usize> {
+            self.store.write(key, column, buf)
+        }
+
+        fn replace(
+            &self,
+            key: &[u8],
+            column: Column,
+            buf: &[u8],
+        ) -> DatabaseResult<(usize, Option<Value>)> {
+            self.store.replace(key, column, buf)
+        }
+
+        fn take(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>> {
+            self.store.take(key, column)
+        }
+
+        fn delete(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>> {
+            self.store.delete(key, column)
+        }
+
+        fn exists(&self, key: &[u8], column: Column) -> DatabaseResult<bool> {
+            self.store.exists(key, column)
+        }
+
+        fn size_of_value(
+            &self,
+            key: &[u8],
+            column: Column,
+        ) -> DatabaseResult<Option<usize>> {
+            self.store.size_of_value(key, column)
+        }
+
+        fn get(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>> {
+            self.store.get(key, column)
+        }
+
+        fn read(
+            &self,
+            key: &[u8],
+            column: Column,
+            buf: &mut [u8],
+        ) -> DatabaseResult<Option<usize>> {
+            self.store.read(key, column, buf)
+        }
+
+        fn read_alloc(
+            &self,
+            key: &[u8],
+            column: Column,
+        ) -> DatabaseResult<Option<Value>> {
+            self.store.read_alloc(key, column)
+        }
+
+        fn iter_all(
+            &self,
+            column: Column,
+            prefix: Option<&[u8]>,
+            start: Option<&[u8]>,
+            direction: IterDirection,
+        ) -> BoxedIter<KVItem> {
+            KeyValueStore::iter_all(&self.store, column, prefix, start, direction)
+        }
+    }
+
+    impl BatchOperations for FailingStore {
+        fn batch_write(
+            &self,
+            entries: &mut dyn Iterator<Item = (Vec<u8>, Column, WriteOperation)>,
+        ) -> DatabaseResult<()> {
+            if self.fail.load(AtomicOrdering::SeqCst) {
+                return Err(DatabaseError::Other(anyhow::anyhow!("injected failure")))
+            }
+            self.store.batch_write(entries)
+        }
+    }
+
+    impl TransactableStorage for FailingStore {}
+
+    #[test]
+    fn failed_commit_keeps_the_changes() {
+        let store = Arc::new(FailingStore::default());
+        store.fail.store(true, AtomicOrdering::SeqCst);
+        let view = MemoryTransactionView::new(store.clone());
+        let expected = Arc::new(vec![1, 2, 3]);
+        view.put(&[0xA], Column::Metadata, expected.clone())
+            .unwrap();
+        view.put(&[0xB], Column::Coins, expected.clone()).unwrap();
+
+        assert!(view.commit().is_err());
+        assert!(!store.exists(&[0xA], Column::Metadata).unwrap());
+        assert!(!store.exists(&[0xB], Column::Coins).unwrap());
+        assert_eq!(
+            view.get(&[0xA], Column::Metadata).unwrap(),
+            Some(expected.clone())
+        );
+
+        // The retry writes all changes.
+        store.fail.store(false, AtomicOrdering::SeqCst);
+        view.commit().unwrap();
+        assert_eq!(
+            store.get(&[0xA], Column::Metadata).unwrap(),
+            Some(expected.clone())
+        );
+        assert_eq!(store.get(&[0xB], Column::Coins).unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn batch_write_is_visible_in_the_view_only() {
+        let store = Arc::new(MemoryStore::default());
+        let view = MemoryTransactionView::new(store.clone());
+        store.put(&[0xB], Column::Coins, Arc::new(vec![1])).unwrap();
+
+        let ops = vec![
+            (
+                vec![0xA],
+                Column::Metadata,
+                WriteOperation::Insert(Arc::new(vec![2])),
+            ),
+            (vec![0xB], Column::Coins, WriteOperation::Remove),
+        ];
+        view.batch_write(&mut ops.into_iter()).unwrap();
+
+        assert_eq!(
+            view.get(&[0xA], Column::Metadata).unwrap(),
+            Some(Arc::new(vec![2]))
+        );
+        assert_eq!(view.get(&[0xB], Column::Coins).unwrap(), None);
+        assert!(store.exists(&[0xB], Column::Coins).unwrap());
+
+        view.commit().unwrap();
+        assert!(store.exists(&[0xA], Column::Metadata).unwrap());
+        assert!(!store.exists(&[0xB], Column::Coins).unwrap());
+    }
 
     #[test]
     fn get_returns_from_view() {
@@ -6,6 +6,12 @@ use crate::{
         Result as DatabaseResult,
     },
     state::{
+        rocks_db_config::{
+            ColumnConfig,
+            CompactionStyle,
+            Compression,
+            RocksDbConfig,
+        },
         BatchOperations,
         IterDirection,
         KVItem,
@@ -16,15 +22,24 @@ use crate::{
     },
 };
 #[cfg(feature = "metrics")]
-use fuel_core_metrics::core_metrics::DATABASE_METRICS;
+use fuel_core_metrics::core_metrics::{
+    CacheLabel,
+    ColumnLabel,
+    ColumnMetrics,
+    DatabaseMetrics,
+    DATABASE_METRICS,
+};
 use fuel_core_storage::iter::{
     BoxedIter,
     IntoBoxedIter,
 };
 use rocksdb::{
+    checkpoint::Checkpoint,
+    BlockBasedOptions,
     BoundColumnFamily,
     Cache,
     ColumnFamilyDescriptor,
+    DBCompactionStyle,
     DBCompressionType,
     DBWithThreadMode,
     IteratorMode,
@@ -43,7 +58,10 @@ use std::{
 type DB = DBWithThreadMode<MultiThreaded>;
 #[derive(Debug)]
 pub struct RocksDb {
-    db: DB,
+    db: Arc<DB>,
+    /// The metrics of each column indexed by [`Column::as_usize`].
+    #[cfg(feature = "metrics")]
+    metrics: Vec<ColumnMetrics>,
 }
 
 impl RocksDb {
@@ -63,28 +81,37 @@ impl RocksDb {
         columns: Vec<Column>,
         capacity: Option<usize>,
     ) -> DatabaseResult<RocksDb> {
+        Self::open_with_config(path, columns, capacity, &RocksDbConfig::default())
+    }
+
+    /// Opens the database with the column families tuned by the `config`.
+    pub fn open_with_config<P: AsRef<Path>>(
+        path: P,
+        columns: Vec<Column>,
+        capacity: Option<usize>,
+        config: &RocksDbConfig,
+    ) -> DatabaseResult<RocksDb> {
+        let cf_opts = Self::columns_opts(&columns, config)?;
         let cf_descriptors: Vec<_> = columns
-            .clone()
-            .into_iter()
-            .map(|i| ColumnFamilyDescriptor::new(RocksDb::col_name(i), Self::cf_opts(i)))
+            .iter()
+            .zip(cf_opts.iter())
+            .map(|(column, opts)| {
+                ColumnFamilyDescriptor::new(RocksDb::col_name(*column), opts.clone())
+            })
             .collect();
 
-        let mut opts = Options::default();
+        let mut opts = Self::db_opts(capacity);
         opts.create_if_missing(true);
-        opts.set_compression_type(DBCompressionType::Lz4);
-        if let Some(capacity) = capacity {
-            let cache = Cache::new_lru_cache(capacity);
-            opts.set_row_cache(&cache);
-        }
+        // The column families added by the newer versions are created on the first open.
+        opts.create_missing_column_families(true);
 
         let db = match DB::open_cf_descriptors(&opts, &path, cf_descriptors) {
             Err(_) => {
                 // setup cfs
                 match DB::open_cf(&opts, &path, &[] as &[&str]) {
                     Ok(db) => {
-                        for i in columns {
-                            let opts = Self::cf_opts(i);
-                            db.create_cf(RocksDb::col_name(i), &opts)
+                        for (column, opts) in columns.iter().zip(cf_opts.iter()) {
+                            db.create_cf(RocksDb::col_name(*column), opts)
                                 .map_err(|e| DatabaseError::Other(e.into()))?;
                         }
                         Ok(db)
@@ -95,24 +122,236 @@ impl RocksDb {
             ok => ok,
         }
         .map_err(|e| DatabaseError::Other(e.into()))?;
-        let rocks_db = RocksDb { db };
+        let rocks_db = RocksDb::new(db, opts, columns);
         Ok(rocks_db)
     }
 
-    fn cf(&self, column: Column) -> Arc<BoundColumnFamily> {
+    /// Opens the read-only secondary instance of the database created by the primary
+    /// instance at the `path`. The secondary instance sees the writes of the primary
+    /// instance after [`RocksDb::catch_up_with_primary`]. The `secondary_path` keeps
+    /// the info logs of the secondary instance and must differ from the `path`.
+    pub fn open_secondary<P: AsRef<Path>, S: AsRef<Path>>(
+        path: P,
+        secondary_path: S,
+        columns: Vec<Column>,
+        capacity: Option<usize>,
+        config: &RocksDbConfig,
+    ) -> DatabaseResult<RocksDb> {
+        let cf_descriptors: Vec<_> = columns
+            .iter()
+            .zip(Self::columns_opts(&columns, config)?)
+            .map(|(column, opts)| {
+                ColumnFamilyDescriptor::new(RocksDb::col_name(*column), opts)
+            })
+            .collect();
+
+        let mut opts = Self::db_opts(capacity);
+        // The secondary instance must keep all files of the primary instance open,
+        // otherwise the primary instance may remove them before they are read.
+        opts.set_max_open_files(-1);
+
+        let db = DB::open_cf_descriptors_as_secondary(
+            &opts,
+            path.as_ref(),
+            secondary_path.as_ref(),
+            cf_descriptors,
+        )
+        .map_err(|e| DatabaseError::Other(e.into()))?;
+        Ok(RocksDb::new(db, opts, columns))
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn new(db: DB, opts: Options, columns: Vec<Column>) -> RocksDb {
+        let db = Arc::new(db);
+        #[cfg(feature = "metrics")]
+        Self::register_statistics(&db, opts, columns);
+        RocksDb {
+            db,
+            #[cfg(feature = "metrics")]
+            metrics: enum_iterator::all::<Column>()
+                .map(|column| DATABASE_METRICS.column(&format!("{:?}", column)))
+                .collect(),
+        }
+    }
+
+    /// Exports the internal statistics of the database on each scrape of the metrics
+    /// until the database is closed.
+    #[cfg(feature = "metrics")]
+    fn register_statistics(db: &Arc<DB>, opts: Options, columns: Vec<Column>) {
+        let db = Arc::downgrade(db);
+        DATABASE_METRICS.register_statistics_source(Box::new(
+            move |metrics: &DatabaseMetrics| {
+                let Some(db) = db.upgrade() else { return false };
+                for column in &columns {
+                    let Some(cf) = db.cf_handle(&RocksDb::col_name(*column)) else {
+                        continue
+                    };
+                    let label = ColumnLabel {
+                        column: format!("{:?}", column),
+                    };
+                    let property =
+                        |name: &str| db.property_int_value_cf(&cf, name).ok().flatten();
+                    if let Some(bytes) =
+                        property("rocksdb.estimate-pending-compaction-bytes")
+                    {
+                        metrics
+                            .compaction_pending_bytes
+                            .get_or_create(&label)
+                            .set(bytes as i64);
+                    }
+                    if let Some(size) = property("rocksdb.total-sst-files-size") {
+                        metrics
+                            .sst_files_size
+                            .get_or_create(&label)
+                            .set(size as i64);
+                    }
+                }
+
+                if let Some(statistics) = opts.get_statistics() {
+                    for cache in ["block", "row"] {
+                        let hits =
+                            ticker(&statistics, &format!("rocksdb.{cache}.cache.hit"));
+                        let misses =
+                            ticker(&statistics, &format!("rocksdb.{cache}.cache.miss"));
+                        if let (Some(hits), Some(misses)) = (hits, misses) {
+                            if hits + misses > 0 {
+                                let label = CacheLabel {
+                                    cache: cache.to_string(),
+                                };
+                                metrics
+                                    .cache_hit_ratio
+                                    .get_or_create(&label)
+                                    .set(hits as f64 / (hits + misses) as f64);
+                            }
+                        }
+                    }
+                }
+                true
+            },
+        ));
+    }
+
+    #[cfg(feature = "metrics")]
+    fn metrics(&self, column: Column) -> &ColumnMetrics {
+        &self.metrics[column.as_usize()]
+    }
+
+    /// Performs the point lookup in the `column`, measuring its duration.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn lookup<T>(&self, column: Column, lookup: impl FnOnce() -> T) -> T {
+        #[cfg(feature = "metrics")]
+        let start = std::time::Instant::now();
+        let result = lookup();
+        #[cfg(feature = "metrics")]
+        {
+            let metrics = self.metrics(column);
+            metrics.reads.inc();
+            metrics.get_duration.observe(start.elapsed().as_secs_f64());
+        }
+        result
+    }
+
+    fn db_opts(capacity: Option<usize>) -> Options {
+        let mut opts = Options::default();
+        opts.set_compression_type(DBCompressionType::Lz4);
+        #[cfg(feature = "metrics")]
+        opts.enable_statistics();
+        if let Some(capacity) = capacity {
+            let cache = Cache::new_lru_cache(capacity);
+            opts.set_row_cache(&cache);
+        }
+        opts
+    }
+
+    /// Returns the options of the `columns` in the same order.
+    fn columns_opts(
+        columns: &[Column],
+        config: &RocksDbConfig,
+    ) -> DatabaseResult<Vec<Options>> {
+        let column_configs = config.column_configs().map_err(DatabaseError::Other)?;
+        let default_config = ColumnConfig::default();
+        let column_config = |column: Column| {
+            *column_configs
+                .get(&column.as_usize())
+                .unwrap_or(&&default_config)
+        };
+
+        // The columns with the `block_cache_share` get their own part of the block cache,
+        // the rest of the cache is shared by the other columns.
+        let block_caches: Vec<Option<Cache>> = match config.block_cache_size {
+            Some(size) => {
+                let reserved: f64 = columns
+                    .iter()
+                    .filter_map(|column| column_config(*column).block_cache_share)
+                    .sum();
+                let shared =
+                    Cache::new_lru_cache((size as f64 * (1.0 - reserved)) as usize);
+                columns
+                    .iter()
+                    .map(|column| match column_config(*column).block_cache_share {
+                        Some(share) => {
+                            Some(Cache::new_lru_cache((size as f64 * share) as usize))
+                        }
+                        None => Some(shared.clone()),
+                    })
+                    .collect()
+            }
+            None => columns.iter().map(|_| None).collect(),
+        };
+
+        Ok(columns
+            .iter()
+            .zip(block_caches.iter())
+            .map(|(column, cache)| {
+                Self::cf_opts(*column, column_config(*column), cache.as_ref())
+            })
+            .collect())
+    }
+
+    fn cf(&self, column: Column) -> DatabaseResult<Arc<BoundColumnFamily>> {
         self.db
             .cf_handle(&RocksDb::col_name(column))
-            .expect("invalid column state")
+            .ok_or_else(|| {
+                DatabaseError::Other(anyhow::anyhow!(
+                    "The column {:?} is not opened in the database",
+                    column
+                ))
+            })
     }
 
     fn col_name(column: Column) -> String {
         format!("column-{}", column.as_usize())
     }
 
-    fn cf_opts(column: Column) -> Options {
+    fn cf_opts(
+        column: Column,
+        config: &ColumnConfig,
+        block_cache: Option<&Cache>,
+    ) -> Options {
         let mut opts = Options::default();
         opts.create_if_missing(true);
-        opts.set_compression_type(DBCompressionType::Lz4);
+        opts.set_compression_type(match config.compression {
+            None | Some(Compression::Lz4) => DBCompressionType::Lz4,
+            Some(Compression::Lz4hc) => DBCompressionType::Lz4hc,
+            Some(Compression::Zstd) => DBCompressionType::Zstd,
+            Some(Compression::None) => DBCompressionType::None,
+        });
+        if let Some(size) = config.write_buffer_size {
+            opts.set_write_buffer_size(size);
+        }
+        opts.set_compaction_style(match config.compaction_style {
+            None | Some(CompactionStyle::Level) => DBCompactionStyle::Level,
+            Some(CompactionStyle::Universal) => DBCompactionStyle::Universal,
+        });
+
+        let mut block_opts = BlockBasedOptions::default();
+        if let Some(cache) = block_cache {
+            block_opts.set_block_cache(cache);
+        }
+        if let Some(bits) = config.bloom_filter_bits {
+            block_opts.set_bloom_filter(bits, false);
+        }
+        opts.set_block_based_table_factory(&block_opts);
 
         // All double-keys should be configured here
         match column {
@@ -135,39 +374,53 @@ impl RocksDb {
         column: Column,
         opts: ReadOptions,
         iter_mode: IteratorMode,
-    ) -> impl Iterator<Item = KVItem> + '_ {
-        self.db
-            .iterator_cf_opt(&self.cf(column), opts, iter_mode)
-            .map(|item| {
+    ) -> BoxedIter<KVItem> {
+        let cf = match self.cf(column) {
+            Ok(cf) => cf,
+            Err(e) => return iter::once(Err(e)).into_boxed(),
+        };
+        #[cfg(feature = "metrics")]
+        let metrics = self.metrics(column);
+        #[cfg(feature = "metrics")]
+        metrics.iterations.inc();
+        let iter = self
+            .db
+            .iterator_cf_opt(&cf, opts, iter_mode)
+            .map(move |item| {
                 item.map(|(key, value)| {
                     let value_as_vec = Vec::from(value);
                     let key_as_vec = Vec::from(key);
                     #[cfg(feature = "metrics")]
                     {
-                        DATABASE_METRICS.read_meter.inc();
-                        DATABASE_METRICS
+                        metrics.reads.inc();
+                        metrics
                             .bytes_read
                             .observe((key_as_vec.len() + value_as_vec.len()) as f64);
                     }
                     (key_as_vec, Arc::new(value_as_vec))
                 })
                 .map_err(|e| DatabaseError::Other(e.into()))
-            })
+            });
+        #[cfg(feature = "metrics")]
+        let iter = MeteredIter {
+            inner: iter,
+            elapsed: Default::default(),
+            metrics,
+        };
+        iter.into_boxed()
     }
 }
 
 impl KeyValueStore for RocksDb {
     fn get(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>> {
-        #[cfg(feature = "metrics")]
-        DATABASE_METRICS.read_meter.inc();
+        let cf = self.cf(column)?;
         let value = self
-            .db
-            .get_cf(&self.cf(column), key)
+            .lookup(column, || self.db.get_cf(&cf, key))
             .map_err(|e| DatabaseError::Other(e.into()));
         #[cfg(feature = "metrics")]
         {
             if let Ok(Some(value)) = &value {
-                DATABASE_METRICS.bytes_read.observe(value.len() as f64);
+                self.metrics(column).bytes_read.observe(value.len() as f64);
             }
         }
         value.map(|value| value.map(Arc::new))
@@ -181,24 +434,27 @@ impl KeyValueStore for RocksDb {
     ) -> DatabaseResult<Option<Value>> {
         #[cfg(feature = "metrics")]
         {
-            DATABASE_METRICS.write_meter.inc();
-            DATABASE_METRICS.bytes_written.observe(value.len() as f64);
+            let metrics = self.metrics(column);
+            metrics.writes.inc();
+            metrics.bytes_written.observe(value.len() as f64);
         }
         // FIXME: This is a race condition. We should use a transaction.
         let prev = self.get(key, column)?;
         // FIXME: This is a race condition. We should use a transaction.
         self.db
-            .put_cf(&self.cf(column), key, value.as_ref())
+            .put_cf(&self.cf(column)?, key, value.as_ref())
             .map_err(|e| DatabaseError::Other(e.into()))
             .map(|_| prev)
     }
 
     fn delete(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>> {
+        #[cfg(feature = "metrics")]
+        self.metrics(column).writes.inc();
         // FIXME: This is a race condition. We should use a transaction.
         let prev = self.get(key, column)?;
         // FIXME: This is a race condition. We should use a transaction.
         self.db
-            .delete_cf(&self.cf(column), key)
+            .delete_cf(&self.cf(column)?, key)
             .map_err(|e| DatabaseError::Other(e.into()))
             .map(|_| prev)
     }
@@ -206,8 +462,8 @@ impl KeyValueStore for RocksDb {
     fn exists(&self, key: &[u8], column: Column) -> DatabaseResult<bool> {
         // use pinnable mem ref to avoid memcpy of values associated with the key
         // since we're just checking for the existence of the key
-        self.db
-            .get_pinned_cf(&self.cf(column), key)
+        let cf = self.cf(column)?;
+        self.lookup(column, || self.db.get_pinned_cf(&cf, key))
             .map_err(|e| DatabaseError::Other(e.into()))
             .map(|v| v.is_some())
     }
@@ -229,23 +485,43 @@ impl KeyValueStore for RocksDb {
                         IterDirection::Reverse => IteratorMode::End,
                     };
                 self._iter_all(column, ReadOptions::default(), iter_mode)
-                    .into_boxed()
             }
             (Some(prefix), None) => {
-                // start iterating in a certain direction within the keyspace
-                let iter_mode =
-                    IteratorMode::From(prefix, convert_to_rocksdb_direction(direction));
                 let mut opts = ReadOptions::default();
-                opts.set_prefix_same_as_start(true);
+                let iter_mode = match direction {
+                    IterDirection::Forward => {
+                        // start iterating from the first key with the prefix
+                        opts.set_prefix_same_as_start(true);
+                        IteratorMode::From(prefix, rocksdb::Direction::Forward)
+                    }
+                    IterDirection::Reverse => {
+                        // start iterating from the last key with the prefix,
+                        // which is right before the next prefix
+                        if let Some(next_prefix) = next_prefix(prefix) {
+                            opts.set_iterate_upper_bound(next_prefix);
+                        }
+                        IteratorMode::End
+                    }
+                };
 
-                self._iter_all(column, opts, iter_mode).into_boxed()
+                // end iterating when we've gone outside the prefix, the column
+                // may have no prefix extractor to stop the iteration
+                let prefix = prefix.to_vec();
+                self._iter_all(column, opts, iter_mode)
+                    .take_while(move |item| {
+                        if let Ok((key, _)) = item {
+                            key.starts_with(prefix.as_slice())
+                        } else {
+                            true
+                        }
+                    })
+                    .into_boxed()
             }
             (None, Some(start)) => {
                 // start iterating in a certain direction from the start key
                 let iter_mode =
                     IteratorMode::From(start, convert_to_rocksdb_direction(direction));
                 self._iter_all(column, ReadOptions::default(), iter_mode)
-                    .into_boxed()
             }
             (Some(prefix), Some(start)) => {
                 // TODO: Maybe we want to allow the `start` to be without a `prefix` in the future.
@@ -273,12 +549,9 @@ impl KeyValueStore for RocksDb {
     }
 
     fn size_of_value(&self, key: &[u8], column: Column) -> DatabaseResult<Option<usize>> {
-        #[cfg(feature = "metrics")]
-        DATABASE_METRICS.read_meter.inc();
-
+        let cf = self.cf(column)?;
         Ok(self
-            .db
-            .get_pinned_cf(&self.cf(column), key)
+            .lookup(column, || self.db.get_pinned_cf(&cf, key))
             .map_err(|e| DatabaseError::Other(e.into()))?
             .map(|value| value.len()))
     }
@@ -289,12 +562,9 @@ impl KeyValueStore for RocksDb {
         column: Column,
         mut buf: &mut [u8],
     ) -> DatabaseResult<Option<usize>> {
-        #[cfg(feature = "metrics")]
-        DATABASE_METRICS.read_meter.inc();
-
+        let cf = self.cf(column)?;
         let r = self
-            .db
-            .get_pinned_cf(&self.cf(column), key)
+            .lookup(column, || self.db.get_pinned_cf(&cf, key))
             .map_err(|e| DatabaseError::Other(e.into()))?
             .map(|value| {
                 let read = value.len();
@@ -307,7 +577,7 @@ impl KeyValueStore for RocksDb {
         #[cfg(feature = "metrics")]
         {
             if let Some(r) = &r {
-                DATABASE_METRICS.bytes_read.observe(*r as f64);
+                self.metrics(column).bytes_read.observe(*r as f64);
             }
         }
         Ok(r)
@@ -316,32 +586,30 @@ impl KeyValueStore for RocksDb {
     fn write(&self, key: &[u8], column: Column, buf: &[u8]) -> DatabaseResult<usize> {
         #[cfg(feature = "metrics")]
         {
-            DATABASE_METRICS.write_meter.inc();
-            DATABASE_METRICS.bytes_written.observe(buf.len() as f64);
+            let metrics = self.metrics(column);
+            metrics.writes.inc();
+            metrics.bytes_written.observe(buf.len() as f64);
         }
 
         let r = buf.len();
         self.db
-            .put_cf(&self.cf(column), key, buf)
+            .put_cf(&self.cf(column)?, key, buf)
             .map_err(|e| DatabaseError::Other(e.into()))?;
 
         Ok(r)
     }
 
     fn read_alloc(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>> {
-        #[cfg(feature = "metrics")]
-        DATABASE_METRICS.read_meter.inc();
-
+        let cf = self.cf(column)?;
         let r = self
-            .db
-            .get_pinned_cf(&self.cf(column), key)
+            .lookup(column, || self.db.get_pinned_cf(&cf, key))
             .map_err(|e| DatabaseError::Other(e.into()))?
             .map(|value| value.to_vec());
 
         #[cfg(feature = "metrics")]
         {
             if let Some(r) = &r {
-                DATABASE_METRICS.bytes_read.observe(r.len() as f64);
+                self.metrics(column).bytes_read.observe(r.len() as f64);
             }
         }
         Ok(r.map(Arc::new))
@@ -362,11 +630,13 @@ impl KeyValueStore for RocksDb {
     }
 
     fn take(&self, key: &[u8], column: Column) -> DatabaseResult<Option<Value>> {
+        #[cfg(feature = "metrics")]
+        self.metrics(column).writes.inc();
         // FIXME: This is a race condition. We should use a transaction.
         let prev = self.read_alloc(key, column)?;
         // FIXME: This is a race condition. We should use a transaction.
         self.db
-            .delete_cf(&self.cf(column), key)
+            .delete_cf(&self.cf(column)?, key)
             .map_err(|e| DatabaseError::Other(e.into()))
             .map(|_| prev)
     }
@@ -377,32 +647,110 @@ impl BatchOperations for RocksDb {
         &self,
         entries: &mut dyn Iterator<Item = (Vec<u8>, Column, WriteOperation)>,
     ) -> DatabaseResult<()> {
+        // Nothing is written until the whole batch is built, so an invalid entry
+        // fails the batch without applying any of its changes.
         let mut batch = WriteBatch::default();
 
         for (key, column, op) in entries {
+            #[cfg(feature = "metrics")]
+            self.metrics(column).writes.inc();
             match op {
                 WriteOperation::Insert(value) => {
-                    batch.put_cf(&self.cf(column), key, value.as_ref());
+                    #[cfg(feature = "metrics")]
+                    self.metrics(column)
+                        .bytes_written
+                        .observe((key.len() + value.len()) as f64);
+                    batch.put_cf(&self.cf(column)?, key, value.as_ref());
                 }
                 WriteOperation::Remove => {
-                    batch.delete_cf(&self.cf(column), key);
+                    batch.delete_cf(&self.cf(column)?, key);
                 }
             }
         }
         #[cfg(feature = "metrics")]
-        {
-            DATABASE_METRICS.write_meter.inc();
-            DATABASE_METRICS
-                .bytes_written
-                .observe(batch.size_in_bytes() as f64);
-        }
-        self.db
+        let start = std::time::Instant::now();
+        let result = self
+            .db
             .write(batch)
+            .map_err(|e| DatabaseError::Other(e.into()));
+        #[cfg(feature = "metrics")]
+        DATABASE_METRICS
+            .batch_write_duration
+            .observe(start.elapsed().as_secs_f64());
+        result
+    }
+}
+
+impl TransactableStorage for RocksDb {
+    /// The files of the checkpoint are hard-linked if the `path` is on the same
+    /// filesystem, so the checkpoint is cheap.
+    fn checkpoint(&self, path: &Path) -> DatabaseResult<()> {
+        Checkpoint::new(self.db.as_ref())
+            .and_then(|checkpoint| checkpoint.create_checkpoint(path))
             .map_err(|e| DatabaseError::Other(e.into()))
     }
+
+    fn catch_up_with_primary(&self) -> DatabaseResult<()> {
+        self.db
+            .try_catch_up_with_primary()
+            .map_err(|e| DatabaseError::Other(e.into()))
+    }
+}
+
+/// Measures the time spent in the database while iterating. The duration is observed
+/// when the iteration is dropped.
+#[cfg(feature = "metrics")]
+struct MeteredIter<'a, I> {
+    inner: I,
+    elapsed: std::time::Duration,
+    metrics: &'a ColumnMetrics,
+}
+
+#[cfg(feature = "metrics")]
+impl<'a, I: Iterator> Iterator for MeteredIter<'a, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = std::time::Instant::now();
+        let item = self.inner.next();
+        self.elapsed += start.elapsed();
+        item
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<'a, I> Drop for MeteredIter<'a, I> {
+    fn drop(&mut self) {
+        self.metrics
+            .iter_all_duration
+            .observe(self.elapsed.as_secs_f64());
+    }
 }
 
-impl TransactableStorage for RocksDb {}
+/// Returns the value of the `ticker` from the statistics of the database,
+/// formatted as the lines like `rocksdb.block.cache.hit COUNT : 42`.
+#[cfg(feature = "metrics")]
+fn ticker(statistics: &str, ticker: &str) -> Option<u64> {
+    statistics.lines().find_map(|line| {
+        line.strip_prefix(ticker)?
+            .strip_prefix(" COUNT : ")?
+            .trim()
+            .parse()
+            .ok()
+    })
+}
+
+/// Returns the smallest key greater than all keys with the `prefix`, if any.
+fn next_prefix(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut next = prefix.to_vec();
+    while let Some(last) = next.pop() {
+        if last < u8::MAX {
+            next.push(last + 1);
+            return Some(next)
+        }
+    }
+    None
+}
 
 #[cfg(test)]
 mod tests {
@@ -417,6 +765,20 @@ mod tests {
         )
     }
 
+    /// Keeps the temporary directory of the database alive.
+    struct TestRocksDb(RocksDb, TempDir);
+
+    impl AsRef<RocksDb> for TestRocksDb {
+        fn as_ref(&self) -> &RocksDb {
+            &self.0
+        }
+    }
+
+    fuel_core_database::storage_conformance_tests!(|| {
+        let (db, tmp_dir) = create_db();
+        TestRocksDb(db, tmp_dir)
+    });
+
     #[test]
     fn can_put_and_read() {
         let key = vec![0xA, 0xB, 0xC];
@@ -575,4 +937,137 @@ mod tests {
 
         assert!(!db.exists(&key, Column::Metadata).unwrap());
     }
+
+    #[test]
+    fn failed_batch_write_applies_nothing() {
+        let tmp_dir = TempDir::new().unwrap();
+        let db = RocksDb::open(tmp_dir.path(), vec![Column::Metadata], None).unwrap();
+        let key = vec![0xA, 0xB, 0xC];
+
+        let ops = vec![
+            (
+                key.clone(),
+                Column::Metadata,
+                WriteOperation::Insert(Arc::new(vec![1, 2, 3])),
+            ),
+            // The column isn't opened, so the batch can't be written.
+            (
+                key.clone(),
+                Column::Coins,
+                WriteOperation::Insert(Arc::new(vec![4, 5, 6])),
+            ),
+        ];
+        let result = db.batch_write(&mut ops.into_iter());
+
+        assert!(result.is_err());
+        assert!(!db.exists(&key, Column::Metadata).unwrap());
+    }
+
+    #[test]
+    fn can_open_with_tuned_columns() {
+        let tmp_dir = TempDir::new().unwrap();
+        let config = RocksDbConfig {
+            block_cache_size: Some(1024 * 1024),
+            columns: [
+                (
+                    "ContractsState".to_string(),
+                    ColumnConfig {
+                        block_cache_share: Some(0.5),
+                        bloom_filter_bits: Some(10.0),
+                        write_buffer_size: Some(1024 * 1024),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "Receipts".to_string(),
+                    ColumnConfig {
+                        compression: Some(Compression::Zstd),
+                        compaction_style: Some(CompactionStyle::Universal),
+                        ..Default::default()
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let columns = enum_iterator::all::<Column>().collect();
+        let db =
+            RocksDb::open_with_config(tmp_dir.path(), columns, None, &config).unwrap();
+
+        let expected = Arc::new(vec![1, 2, 3]);
+        db.put(&[0xA], Column::ContractsState, expected.clone())
+            .unwrap();
+        db.put(&[0xB], Column::Receipts, expected.clone()).unwrap();
+        assert_eq!(
+            db.get(&[0xA], Column::ContractsState).unwrap(),
+            Some(expected.clone())
+        );
+        assert_eq!(db.get(&[0xB], Column::Receipts).unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn secondary_sees_the_primary_writes_after_catch_up() {
+        let primary_dir = TempDir::new().unwrap();
+        let secondary_dir = TempDir::new().unwrap();
+        let columns: Vec<_> = enum_iterator::all::<Column>().collect();
+        let primary = RocksDb::open(primary_dir.path(), columns.clone(), None).unwrap();
+        let secondary = RocksDb::open_secondary(
+            primary_dir.path(),
+            secondary_dir.path(),
+            columns,
+            None,
+            &RocksDbConfig::default(),
+        )
+        .unwrap();
+
+        let expected = Arc::new(vec![1, 2, 3]);
+        primary
+            .put(&[0xA], Column::Metadata, expected.clone())
+            .unwrap();
+        assert_eq!(secondary.get(&[0xA], Column::Metadata).unwrap(), None);
+
+        secondary.catch_up_with_primary().unwrap();
+        assert_eq!(
+            secondary.get(&[0xA], Column::Metadata).unwrap(),
+            Some(expected)
+        );
+        assert!(secondary
+            .put(&[0xB], Column::Metadata, Arc::new(vec![4]))
+            .is_err());
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn operations_are_metered_per_column() {
+        let (db, _tmp) = create_db();
+        let metrics = db.metrics(Column::Receipts).clone();
+        // The metrics are global, so the other tests may increase them concurrently.
+        let reads = metrics.reads.get();
+        let writes = metrics.writes.get();
+        let iterations = metrics.iterations.get();
+
+        db.put(&[0xA], Column::Receipts, Arc::new(vec![1, 2, 3]))
+            .unwrap();
+        db.get(&[0xA], Column::Receipts).unwrap();
+        let items: Vec<_> = db
+            .iter_all(Column::Receipts, None, None, IterDirection::Forward)
+            .collect();
+        assert_eq!(items.len(), 1);
+
+        assert!(metrics.writes.get() > writes);
+        // The `put` reads the previous value, the `get` and the iteration read one more.
+        assert!(metrics.reads.get() >= reads + 3);
+        assert!(metrics.iterations.get() > iterations);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn tickers_are_parsed_from_statistics() {
+        let statistics = "rocksdb.block.cache.miss COUNT : 3\n\
+                          rocksdb.block.cache.hit COUNT : 42\n\
+                          rocksdb.block.cache.hit.bytes COUNT : 7\n";
+        assert_eq!(ticker(statistics, "rocksdb.block.cache.hit"), Some(42));
+        assert_eq!(ticker(statistics, "rocksdb.block.cache.miss"), Some(3));
+        assert_eq!(ticker(statistics, "rocksdb.row.cache.hit"), None);
+    }
 }
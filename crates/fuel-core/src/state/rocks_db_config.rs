@@ -0,0 +1,150 @@
+//! The tuning of the RocksDB column families.
+//!
+//! The configuration is usually loaded from a TOML file:
+//!
+//! ```toml
+//! block_cache_size = 1073741824
+//!
+//! [columns.ContractsState]
+//! block_cache_share = 0.5
+//! bloom_filter_bits = 10.0
+//! write_buffer_size = 134217728
+//!
+//! [columns.Receipts]
+//! compression = "zstd"
+//! compaction_style = "universal"
+//! ```
+//!
+//! The settings that are not specified keep the defaults of the node.
+
+use crate::database::Column;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+};
+
+/// The tuning of the RocksDB database.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RocksDbConfig {
+    /// The total size of the block cache in bytes. The part of the cache not reserved
+    /// by the `block_cache_share` of the columns is shared by the other columns.
+    /// Each column uses a small block cache of its own if not set.
+    pub block_cache_size: Option<usize>,
+    /// The tuning of the column families keyed by the name of the [`Column`].
+    pub columns: BTreeMap<String, ColumnConfig>,
+}
+
+/// The tuning of the column family.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColumnConfig {
+    /// The share of the `block_cache_size` reserved for the column, between 0 and 1.
+    pub block_cache_share: Option<f64>,
+    /// The number of bits per key of the bloom filter. The filter is disabled if not set.
+    pub bloom_filter_bits: Option<f64>,
+    /// The compression algorithm of the column. LZ4 by default.
+    pub compression: Option<Compression>,
+    /// The size of the memtable of the column in bytes.
+    pub write_buffer_size: Option<usize>,
+    /// The compaction style of the column. Level compaction by default.
+    pub compaction_style: Option<CompactionStyle>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    None,
+    Lz4,
+    Lz4hc,
+    Zstd,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompactionStyle {
+    Level,
+    Universal,
+}
+
+impl RocksDbConfig {
+    /// Returns the tuning of every configured column, checking that the column names
+    /// and the block cache shares are valid.
+    pub fn column_configs(&self) -> anyhow::Result<BTreeMap<usize, &ColumnConfig>> {
+        let mut columns = BTreeMap::new();
+        let mut total_share = 0.0;
+        for (name, config) in &self.columns {
+            let column = Column::from_str(name)
+                .map_err(|_| anyhow::anyhow!("Unknown database column `{}`", name))?;
+
+            if let Some(share) = config.block_cache_share {
+                if self.block_cache_size.is_none() {
+                    anyhow::bail!(
+                        "The `block_cache_share` of the column `{}` requires the `block_cache_size`",
+                        name
+                    );
+                }
+                if !(0.0..=1.0).contains(&share) {
+                    anyhow::bail!(
+                        "The `block_cache_share` of the column `{}` must be between 0 and 1",
+                        name
+                    );
+                }
+                total_share += share;
+            }
+            columns.insert(column.as_usize(), config);
+        }
+
+        if total_share > 1.0 {
+            anyhow::bail!("The sum of the `block_cache_share` of the columns exceeds 1");
+        }
+        Ok(columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(columns: &[(&str, ColumnConfig)]) -> RocksDbConfig {
+        RocksDbConfig {
+            block_cache_size: Some(1024),
+            columns: columns
+                .iter()
+                .map(|(name, config)| (name.to_string(), config.clone()))
+                .collect(),
+        }
+    }
+
+    fn share(share: f64) -> ColumnConfig {
+        ColumnConfig {
+            block_cache_share: Some(share),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn columns_are_configured_by_name() {
+        let config = config(&[("ContractsState", share(0.5)), ("Coins", share(0.5))]);
+        let columns = config.column_configs().unwrap();
+        assert_eq!(columns.len(), 2);
+        assert!(columns.contains_key(&Column::ContractsState.as_usize()));
+        assert!(columns.contains_key(&Column::Coins.as_usize()));
+    }
+
+    #[test]
+    fn unknown_column_is_rejected() {
+        let config = config(&[("Unknown", ColumnConfig::default())]);
+        assert!(config.column_configs().is_err());
+    }
+
+    #[test]
+    fn cache_shares_above_the_cache_size_are_rejected() {
+        let config = config(&[("ContractsState", share(0.7)), ("Coins", share(0.7))]);
+        assert!(config.column_configs().is_err());
+    }
+}
@@ -1,65 +1,188 @@
 use lazy_static::lazy_static;
 use prometheus_client::{
+    encoding::EncodeLabelSet,
     metrics::{
         counter::Counter,
-        histogram::Histogram,
+        family::Family,
+        gauge::Gauge,
+        histogram::{
+            exponential_buckets,
+            Histogram,
+        },
     },
     registry::Registry,
 };
+use std::sync::{
+    atomic::AtomicU64,
+    Mutex,
+};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct ColumnLabel {
+    // the name of the database column
+    pub column: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct CacheLabel {
+    // the name of the database cache
+    pub cache: String,
+}
+
+/// Updates the statistics of the database before the metrics are encoded.
+/// Returns `false` when the database is closed, so the source is removed.
+pub type StatisticsSource = Box<dyn Fn(&DatabaseMetrics) -> bool + Send + Sync>;
 
 pub struct DatabaseMetrics {
     pub registry: Registry,
     // For descriptions of each Counter, see the `new` function where each Counter/Histogram is initialized
-    pub write_meter: Counter,
-    pub read_meter: Counter,
-    pub bytes_written: Histogram,
+    pub reads: Family<ColumnLabel, Counter>,
+    pub writes: Family<ColumnLabel, Counter>,
+    pub iterations: Family<ColumnLabel, Counter>,
+    pub bytes_read: Family<ColumnLabel, Histogram>,
+    pub bytes_written: Family<ColumnLabel, Histogram>,
+    pub get_duration: Family<ColumnLabel, Histogram>,
+    pub iter_all_duration: Family<ColumnLabel, Histogram>,
+    pub batch_write_duration: Histogram,
+    pub compaction_pending_bytes: Family<ColumnLabel, Gauge>,
+    pub sst_files_size: Family<ColumnLabel, Gauge>,
+    pub cache_hit_ratio: Family<CacheLabel, Gauge<f64, AtomicU64>>,
+    statistics_sources: Mutex<Vec<StatisticsSource>>,
+}
+
+/// The metrics of one column, cloned from the families to avoid the label lookup
+/// on each database operation.
+#[derive(Clone, Debug)]
+pub struct ColumnMetrics {
+    pub reads: Counter,
+    pub writes: Counter,
+    pub iterations: Counter,
     pub bytes_read: Histogram,
+    pub bytes_written: Histogram,
+    pub get_duration: Histogram,
+    pub iter_all_duration: Histogram,
 }
 
 impl DatabaseMetrics {
     fn new() -> Self {
         let registry = Registry::default();
 
-        let write_meter: Counter = Counter::default();
-        let read_meter: Counter = Counter::default();
-
-        let bytes_written = Vec::new();
-        let bytes_written_histogram = Histogram::new(bytes_written.into_iter());
-
-        let bytes_read = Vec::new();
-        let bytes_read_histogram = Histogram::new(bytes_read.into_iter());
+        // From 16 bytes to 4 megabytes.
+        fn bytes_histogram() -> Histogram {
+            Histogram::new(exponential_buckets(16.0, 4.0, 10))
+        }
+        // From 10 microseconds to 2.6 seconds.
+        fn duration_histogram() -> Histogram {
+            Histogram::new(exponential_buckets(0.000_01, 4.0, 10))
+        }
 
         DatabaseMetrics {
             registry,
-            write_meter,
-            read_meter,
-            bytes_read: bytes_read_histogram,
-            bytes_written: bytes_written_histogram,
+            reads: Family::default(),
+            writes: Family::default(),
+            iterations: Family::default(),
+            bytes_read: Family::new_with_constructor(bytes_histogram),
+            bytes_written: Family::new_with_constructor(bytes_histogram),
+            get_duration: Family::new_with_constructor(duration_histogram),
+            iter_all_duration: Family::new_with_constructor(duration_histogram),
+            batch_write_duration: duration_histogram(),
+            compaction_pending_bytes: Family::default(),
+            sst_files_size: Family::default(),
+            cache_hit_ratio: Family::default(),
+            statistics_sources: Mutex::new(Vec::new()),
         }
     }
+
+    /// Returns the metrics of the `column`.
+    pub fn column(&self, column: &str) -> ColumnMetrics {
+        let label = ColumnLabel {
+            column: column.to_string(),
+        };
+        ColumnMetrics {
+            reads: self.reads.get_or_create(&label).clone(),
+            writes: self.writes.get_or_create(&label).clone(),
+            iterations: self.iterations.get_or_create(&label).clone(),
+            bytes_read: self.bytes_read.get_or_create(&label).clone(),
+            bytes_written: self.bytes_written.get_or_create(&label).clone(),
+            get_duration: self.get_duration.get_or_create(&label).clone(),
+            iter_all_duration: self.iter_all_duration.get_or_create(&label).clone(),
+        }
+    }
+
+    /// Registers the `source` of the statistics that are not tracked per operation,
+    /// like the sizes of the files or the cache hits of the database.
+    pub fn register_statistics_source(&self, source: StatisticsSource) {
+        self.statistics_sources
+            .lock()
+            .expect("The statistics sources are not poisoned")
+            .push(source);
+    }
+
+    /// Updates the statistics from the registered sources.
+    pub fn refresh_statistics(&self) {
+        self.statistics_sources
+            .lock()
+            .expect("The statistics sources are not poisoned")
+            .retain(|source| source(self));
+    }
 }
 
 pub fn init(mut metrics: DatabaseMetrics) -> DatabaseMetrics {
     metrics.registry.register(
-        "Database_Writes",
-        "Number of database write operations",
-        metrics.write_meter.clone(),
+        "database_writes",
+        "Number of database write operations per column",
+        metrics.writes.clone(),
+    );
+    metrics.registry.register(
+        "database_reads",
+        "Number of database read operations per column, including the iterated entries",
+        metrics.reads.clone(),
     );
     metrics.registry.register(
-        "Database_Reads",
-        "Number of database read operations",
-        metrics.read_meter.clone(),
+        "database_iterations",
+        "Number of database iterations per column",
+        metrics.iterations.clone(),
     );
     metrics.registry.register(
-        "Bytes_Read",
+        "database_bytes_read",
         "Histogram containing values of amount of bytes read per operation",
         metrics.bytes_read.clone(),
     );
     metrics.registry.register(
-        "Bytes_Written",
+        "database_bytes_written",
         "Histogram containing values of amount of bytes written per operation",
         metrics.bytes_written.clone(),
     );
+    metrics.registry.register(
+        "database_get_duration_seconds",
+        "Histogram of the duration of the point lookups per column",
+        metrics.get_duration.clone(),
+    );
+    metrics.registry.register(
+        "database_iter_all_duration_seconds",
+        "Histogram of the time spent in the database per iteration and column",
+        metrics.iter_all_duration.clone(),
+    );
+    metrics.registry.register(
+        "database_batch_write_duration_seconds",
+        "Histogram of the duration of the atomic batch writes",
+        metrics.batch_write_duration.clone(),
+    );
+    metrics.registry.register(
+        "database_compaction_pending_bytes",
+        "Estimated number of bytes the compaction needs to rewrite per column",
+        metrics.compaction_pending_bytes.clone(),
+    );
+    metrics.registry.register(
+        "database_sst_files_size_bytes",
+        "Total size of the SST files per column",
+        metrics.sst_files_size.clone(),
+    );
+    metrics.registry.register(
+        "database_cache_hit_ratio",
+        "Ratio of the cache hits to all cache lookups since the database was opened",
+        metrics.cache_hit_ratio.clone(),
+    );
 
     metrics
 }
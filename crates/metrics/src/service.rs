@@ -1,4 +1,5 @@
 use crate::{
+    core_metrics::DATABASE_METRICS,
     graphql_metrics::GRAPHQL_METRICS,
     p2p_metrics::P2P_METRICS,
     txpool_metrics::TXPOOL_METRICS,
@@ -36,6 +37,11 @@ pub fn encode_metrics_response() -> impl IntoResponse {
         return error_body()
     }
 
+    DATABASE_METRICS.refresh_statistics();
+    if encode(&mut encoded, &DATABASE_METRICS.registry).is_err() {
+        return error_body()
+    }
+
     Response::builder()
         .status(200)
         .body(Body::from(encoded))
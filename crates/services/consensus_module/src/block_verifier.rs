@@ -59,16 +59,27 @@ where
         block: &Block,
     ) -> anyhow::Result<()> {
         match consensus {
-            Consensus::Genesis(_) => {
-                let expected_genesis_height = self
-                    .config
-                    .chain_config
-                    .initial_state
-                    .as_ref()
-                    .map(|config| config.height.unwrap_or_else(|| 0u32.into()))
-                    .unwrap_or_else(|| 0u32.into());
-                verify_genesis_block_fields(expected_genesis_height, block.header())
-            }
+            Consensus::Genesis(_) => match &self.config.last_block {
+                Some(last_block) => verify_genesis_block_fields(
+                    last_block.genesis_height()?,
+                    last_block.blocks_root,
+                    block.header(),
+                ),
+                None => {
+                    let expected_genesis_height = self
+                        .config
+                        .chain_config
+                        .initial_state
+                        .as_ref()
+                        .map(|config| config.height.unwrap_or_else(|| 0u32.into()))
+                        .unwrap_or_else(|| 0u32.into());
+                    verify_genesis_block_fields(
+                        expected_genesis_height,
+                        Bytes32::zeroed(),
+                        block.header(),
+                    )
+                }
+            },
             Consensus::PoA(_) => fuel_core_poa::verifier::verify_block_fields(
                 &self.config.poa,
                 &self.database,
@@ -108,13 +119,14 @@ where
 
 fn verify_genesis_block_fields(
     expected_genesis_height: BlockHeight,
+    expected_prev_root: Bytes32,
     header: &BlockHeader,
 ) -> anyhow::Result<()> {
     let actual_genesis_height = *header.height();
 
     ensure!(
-        header.prev_root() == &Bytes32::zeroed(),
-        "The genesis previous root should be zeroed"
+        header.prev_root() == &expected_prev_root,
+        "The genesis previous root should be zeroed or link to the previous chain"
     );
     ensure!(
         header.time() == Tai64::UNIX_EPOCH,
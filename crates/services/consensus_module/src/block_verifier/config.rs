@@ -2,7 +2,10 @@
 
 use std::time::Duration;
 
-use fuel_core_chain_config::ChainConfig;
+use fuel_core_chain_config::{
+    ChainConfig,
+    LastBlockConfig,
+};
 use fuel_core_poa::verifier::Config as PoAVerifierConfig;
 use fuel_core_types::blockchain::primitives::DaBlockHeight;
 
@@ -10,6 +13,9 @@ use fuel_core_types::blockchain::primitives::DaBlockHeight;
 pub struct Config {
     /// The chain configuration.
     pub chain_config: ChainConfig,
+    /// The last block of the previous chain, if this chain continues it after
+    /// the regenesis. The genesis block follows and links to it.
+    pub last_block: Option<LastBlockConfig>,
     /// The config of verifier for the PoA.
     pub poa: PoAVerifierConfig,
     /// Config for settings the verifier needs that are related to the relayer.
@@ -39,11 +45,13 @@ impl Config {
     /// Creates the verifier config for all possible consensuses.
     pub fn new(
         chain_config: ChainConfig,
+        last_block: Option<LastBlockConfig>,
         enabled_manual_blocks: bool,
         relayer: RelayerVerifierConfig,
     ) -> Self {
         Self {
             chain_config,
+            last_block,
             poa: PoAVerifierConfig {
                 enabled_manual_blocks,
             },
@@ -9,7 +9,7 @@ use test_case::test_case;
         h.consensus.height = 0u32.into();
         h
     },
-    0 => matches Ok(_) ; "Correct header at `0`"
+    0, 0 => matches Ok(_) ; "Correct header at `0`"
 )]
 #[test_case(
     {
@@ -19,7 +19,7 @@ use test_case::test_case;
         h.consensus.height = 113u32.into();
         h
     },
-    113 => matches Ok(_) ; "Correct header at `113`"
+    113, 0 => matches Ok(_) ; "Correct header at `113`"
 )]
 #[test_case(
     {
@@ -29,7 +29,7 @@ use test_case::test_case;
         h.consensus.height = 0u32.into();
         h
     },
-    10 => matches Err(_) ; "wrong expected height"
+    10, 0 => matches Err(_) ; "wrong expected height"
 )]
 #[test_case(
     {
@@ -39,7 +39,7 @@ use test_case::test_case;
         h.consensus.height = 5u32.into();
         h
     },
-    0 => matches Err(_) ; "wrong header height"
+    0, 0 => matches Err(_) ; "wrong header height"
 )]
 #[test_case(
     {
@@ -49,7 +49,7 @@ use test_case::test_case;
         h.consensus.height = 0u32.into();
         h
     },
-    0 => matches Err(_) ; "wrong time"
+    0, 0 => matches Err(_) ; "wrong time"
 )]
 #[test_case(
     {
@@ -59,11 +59,36 @@ use test_case::test_case;
         h.consensus.height = 0u32.into();
         h
     },
-    0 => matches Err(_) ; "wrong root"
+    0, 0 => matches Err(_) ; "wrong root"
+)]
+#[test_case(
+    {
+        let mut h = BlockHeader::default();
+        h.consensus.prev_root = Bytes32::from([1u8; 32]);
+        h.consensus.time = Tai64::UNIX_EPOCH;
+        h.consensus.height = 11u32.into();
+        h
+    },
+    11, 1 => matches Ok(_) ; "Correct header linked to the previous chain"
+)]
+#[test_case(
+    {
+        let mut h = BlockHeader::default();
+        h.consensus.prev_root = Bytes32::zeroed();
+        h.consensus.time = Tai64::UNIX_EPOCH;
+        h.consensus.height = 11u32.into();
+        h
+    },
+    11, 1 => matches Err(_) ; "not linked to the previous chain"
 )]
 fn test_verify_genesis_block_fields(
     header: BlockHeader,
     expected_genesis_height: u32,
+    expected_prev_root: u8,
 ) -> anyhow::Result<()> {
-    verify_genesis_block_fields(expected_genesis_height.into(), &header)
+    verify_genesis_block_fields(
+        expected_genesis_height.into(),
+        Bytes32::from([expected_prev_root; 32]),
+        &header,
+    )
 }
@@ -18,4 +18,6 @@ pub struct Config {
     pub backtrace: bool,
     /// Default mode for utxo_validation
     pub utxo_validation_default: bool,
+    /// Record the values of the state before each block to query it at the past heights.
+    pub archive: bool,
 }
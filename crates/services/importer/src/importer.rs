@@ -169,7 +169,6 @@ where
         let (result, mut db_tx) = result.into();
         let block = &result.sealed_block.entity;
         let consensus = &result.sealed_block.consensus;
-        let block_id = block.id();
         let actual_next_height = *block.header().height();
 
         // During importing of the genesis block, the database should not be initialized
@@ -220,7 +219,7 @@ where
         }
 
         db_after_execution
-            .seal_block(&block_id, &result.sealed_block.consensus)?
+            .seal_block(&expected_next_height, &result.sealed_block.consensus)?
             .should_be_unique(&expected_next_height)?;
 
         db_tx.commit()?;
@@ -22,7 +22,6 @@ use fuel_core_types::{
     blockchain::{
         block::Block,
         consensus::Consensus,
-        primitives::BlockId,
         SealedBlock,
     },
     fuel_tx::Transaction,
@@ -56,7 +55,7 @@ mockall::mock! {
     impl ExecutorDatabase for Database {
         fn seal_block(
             &mut self,
-            block_id: &BlockId,
+            height: &BlockHeight,
             consensus: &Consensus,
         ) -> StorageResult<Option<Consensus>>;
     }
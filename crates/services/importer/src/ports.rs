@@ -6,7 +6,6 @@ use fuel_core_types::{
     blockchain::{
         block::Block,
         consensus::Consensus,
-        primitives::BlockId,
     },
     fuel_types::BlockHeight,
     services::executor::{
@@ -37,11 +36,11 @@ pub trait ImporterDatabase {
 
 /// The port for returned database from the executor.
 pub trait ExecutorDatabase: ImporterDatabase {
-    /// Assigns the `Consensus` data to the block under the `block_id`.
+    /// Assigns the `Consensus` data to the block at the `height`.
     /// Return the previous value at the `height`, if any.
     fn seal_block(
         &mut self,
-        block_id: &BlockId,
+        height: &BlockHeight,
         consensus: &Consensus,
     ) -> StorageResult<Option<Consensus>>;
 }
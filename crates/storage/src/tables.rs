@@ -6,7 +6,6 @@ use fuel_core_types::{
     blockchain::{
         block::CompressedBlock,
         consensus::Consensus,
-        primitives::BlockId,
     },
     entities::{
         coins::coin::CompressedCoin,
@@ -20,6 +19,7 @@ use fuel_core_types::{
         UtxoId,
     },
     fuel_types::{
+        BlockHeight,
         Bytes32,
         ContractId,
         Nonce,
@@ -37,10 +37,9 @@ pub use fuel_vm_private::storage::{
 pub struct FuelBlocks;
 
 impl Mappable for FuelBlocks {
-    /// Unique identifier of the fuel block.
+    /// The height of the fuel block.
     type Key = Self::OwnedKey;
-    // TODO: Seems it would be faster to use `BlockHeight` as primary key.
-    type OwnedKey = BlockId;
+    type OwnedKey = BlockHeight;
     type Value = Self::OwnedValue;
     type OwnedValue = CompressedBlock;
 }
@@ -73,8 +72,9 @@ impl Mappable for Receipts {
 pub struct SealedBlockConsensus;
 
 impl Mappable for SealedBlockConsensus {
+    /// The height of the sealed block.
     type Key = Self::OwnedKey;
-    type OwnedKey = BlockId;
+    type OwnedKey = BlockHeight;
     type Value = Self::OwnedValue;
     type OwnedValue = Consensus;
 }
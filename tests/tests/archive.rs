@@ -0,0 +1,146 @@
+use crate::helpers::transfer;
+use fuel_core::{
+    chain_config::{
+        CoinConfig,
+        ContractConfig,
+        StateConfig,
+    },
+    service::{
+        Config,
+        FuelService,
+    },
+};
+use fuel_core_client::client::{
+    types::primitives::{
+        Address,
+        AssetId,
+        Bytes32,
+        ContractId,
+    },
+    FuelClient,
+};
+
+fn config_with_coins(owner: Address, amounts: &[u64]) -> Config {
+    let mut config = Config::local_node();
+    config.chain_conf.initial_state = Some(StateConfig {
+        coins: Some(
+            amounts
+                .iter()
+                .map(|amount| CoinConfig {
+                    tx_id: None,
+                    output_index: None,
+                    tx_pointer_block_height: None,
+                    tx_pointer_tx_idx: None,
+                    maturity: None,
+                    owner,
+                    amount: *amount,
+                    asset_id: AssetId::BASE,
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    });
+    config
+}
+
+#[tokio::test]
+async fn balance_at_past_heights_is_returned_in_archive_mode() {
+    let owner = Address::default();
+    let recipient = Address::new([1; 32]);
+    let mut config = config_with_coins(owner, &[100, 200]);
+    config.archive = true;
+    let srv = FuelService::new_node(config).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    transfer(&client, &owner, recipient, 150).await;
+    transfer(&client, &owner, recipient, 50).await;
+
+    let balance_at = |address, height: u32| {
+        let client = &client;
+        async move {
+            client
+                .balance_at_height(&address, Some(&AssetId::BASE), height.into())
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(balance_at(owner, 0).await, 300);
+    assert_eq!(balance_at(recipient, 0).await, 0);
+    assert_eq!(balance_at(owner, 1).await, 150);
+    assert_eq!(balance_at(recipient, 1).await, 150);
+    assert_eq!(balance_at(owner, 2).await, 100);
+    assert_eq!(balance_at(recipient, 2).await, 200);
+
+    let balance = client.balance(&owner, Some(&AssetId::BASE)).await.unwrap();
+    assert_eq!(balance, 100);
+
+    // The future state is not known yet.
+    let err = client
+        .balance_at_height(&owner, Some(&AssetId::BASE), 3u32.into())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not archived"), "{err}");
+}
+
+#[tokio::test]
+async fn contract_state_at_past_heights_is_returned_in_archive_mode() {
+    let contract_id = ContractId::new([1; 32]);
+    let asset_id = AssetId::new([2; 32]);
+    let slot = Bytes32::new([3; 32]);
+    let value = Bytes32::new([4; 32]);
+    let mut config = Config::local_node();
+    config.archive = true;
+    config.chain_conf.initial_state = Some(StateConfig {
+        contracts: Some(vec![ContractConfig {
+            contract_id,
+            code: vec![],
+            salt: Default::default(),
+            state: Some(vec![(slot, value)]),
+            balances: Some(vec![(asset_id, 1000)]),
+            tx_id: None,
+            output_index: None,
+            tx_pointer_block_height: None,
+            tx_pointer_tx_idx: None,
+        }]),
+        ..Default::default()
+    });
+    let srv = FuelService::new_node(config).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let balance = client
+        .contract_balance_at_height(&contract_id, Some(&asset_id), 0u32.into())
+        .await
+        .unwrap();
+    assert_eq!(balance, 1000);
+
+    let stored = client
+        .contract_storage_slot(&contract_id, &slot, Some(0u32.into()))
+        .await
+        .unwrap();
+    assert_eq!(stored, Some(value));
+    let stored = client
+        .contract_storage_slot(&contract_id, &slot, None)
+        .await
+        .unwrap();
+    assert_eq!(stored, Some(value));
+    let unset = client
+        .contract_storage_slot(&contract_id, &Bytes32::zeroed(), Some(0u32.into()))
+        .await
+        .unwrap();
+    assert_eq!(unset, None);
+}
+
+#[tokio::test]
+async fn past_heights_are_not_available_without_archive_mode() {
+    let owner = Address::default();
+    let srv = FuelService::new_node(config_with_coins(owner, &[100]))
+        .await
+        .unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let err = client
+        .balance_at_height(&owner, Some(&AssetId::BASE), 0u32.into())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not archived"), "{err}");
+}
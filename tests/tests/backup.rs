@@ -0,0 +1,49 @@
+use fuel_core::{
+    chain_config::ChainConfig,
+    database::Database,
+    service::{
+        Config,
+        DbType,
+        FuelService,
+    },
+};
+use fuel_core_client::client::FuelClient;
+use fuel_core_types::fuel_tx::*;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn checkpoint_of_running_node_is_restorable() {
+    let tmp_dir = TempDir::new().unwrap();
+    let mut config = Config::local_node();
+    config.manual_blocks_enabled = true;
+    config.database_type = DbType::RocksDb;
+    config.database_path = tmp_dir.path().join("db");
+    config.database_checkpoint_dir = Some(tmp_dir.path().join("checkpoints"));
+    let srv = FuelService::new_node(config).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let tx = Transaction::default_test_tx();
+    let tx_id = tx.id(&ConsensusParameters::DEFAULT.chain_id);
+    client.submit_and_await_commit(&tx).await.unwrap();
+    let checkpoint = client.create_database_checkpoint().await.unwrap();
+    assert!(checkpoint.starts_with(tmp_dir.path().join("checkpoints")));
+
+    // The node keeps working after the checkpoint.
+    client.produce_blocks(1, None).await.unwrap();
+
+    let restored_path = tmp_dir.path().join("restored");
+    let chain_name = ChainConfig::local_testnet().chain_name;
+    Database::restore(&checkpoint, &restored_path, &chain_name, None).unwrap();
+    let restored = Database::open(&restored_path, None).unwrap();
+    assert_eq!(*restored.latest_height().unwrap(), 1);
+    assert!(restored.get_tx_status(&tx_id).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn checkpoint_requires_checkpoint_dir() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let err = client.create_database_checkpoint().await.unwrap_err();
+    assert!(err.to_string().contains("checkpoint directory"), "{err}");
+}
@@ -43,7 +43,10 @@ use std::{
 #[tokio::test]
 async fn block() {
     // setup test data in the node
-    let block = CompressedBlock::default();
+    let mut block = CompressedBlock::default();
+    block.header_mut().consensus.height = 1u32.into();
+    block.header_mut().recalculate_metadata();
+    let height = *block.header().height();
     let id = block.id();
     let mut db = Database::default();
     // setup server & client
@@ -52,9 +55,9 @@ async fn block() {
         .unwrap();
     let client = FuelClient::from(srv.bound_address);
 
-    db.storage::<FuelBlocks>().insert(&id, &block).unwrap();
+    db.storage::<FuelBlocks>().insert(&height, &block).unwrap();
     db.storage::<SealedBlockConsensus>()
-        .insert(&id, &Consensus::PoA(Default::default()))
+        .insert(&height, &Consensus::PoA(Default::default()))
         .unwrap();
 
     // run test
@@ -10,7 +10,10 @@ use fuel_core::{
         FuelService,
     },
 };
-use fuel_core_client::client::FuelClient;
+use fuel_core_client::client::{
+    types::CoinType,
+    FuelClient,
+};
 use fuel_core_types::{
     fuel_tx::{
         field::Inputs,
@@ -191,3 +194,44 @@ impl Default for TestSetupBuilder {
         }
     }
 }
+
+/// Transfers the `amount` of the base asset from the `owner` to the `to` address and
+/// waits for the commit of the transaction.
+pub async fn transfer(client: &FuelClient, owner: &Address, to: Address, amount: u64) {
+    let coins = client
+        .coins_to_spend(owner, vec![(AssetId::BASE, amount, None)], None)
+        .await
+        .unwrap();
+
+    let mut tx = TransactionBuilder::script(vec![], vec![])
+        .gas_limit(1_000_000)
+        .to_owned();
+    for coin in coins.into_iter().flatten() {
+        if let CoinType::Coin(coin) = coin {
+            tx.add_input(Input::coin_signed(
+                coin.utxo_id,
+                coin.owner,
+                coin.amount,
+                coin.asset_id,
+                Default::default(),
+                0,
+                coin.maturity.into(),
+            ));
+        }
+    }
+    let tx = tx
+        .add_output(Output::Coin {
+            to,
+            amount,
+            asset_id: AssetId::BASE,
+        })
+        .add_output(Output::Change {
+            to: *owner,
+            amount: 0,
+            asset_id: AssetId::BASE,
+        })
+        .add_witness(Default::default())
+        .finalize_as_transaction();
+
+    client.submit_and_await_commit(&tx).await.unwrap();
+}
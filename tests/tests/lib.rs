@@ -1,5 +1,7 @@
 #![deny(unused_must_use)]
 
+mod archive;
+mod backup;
 mod balances;
 mod blocks;
 mod chain;
@@ -13,8 +15,11 @@ mod helpers;
 mod messages;
 mod node_info;
 mod poa;
+mod pruning;
+mod regenesis;
 #[cfg(feature = "relayer")]
 mod relayer;
+mod secondary;
 mod snapshot;
 #[cfg(feature = "p2p")]
 mod sync;
@@ -0,0 +1,70 @@
+use fuel_core::{
+    database::Database,
+    service::{
+        pruner,
+        Config,
+        FuelService,
+    },
+};
+use fuel_core_client::client::FuelClient;
+use fuel_core_types::fuel_tx::*;
+use std::time::Duration;
+
+async fn wait_for_pruned_height(database: &Database, height: u32) {
+    for _ in 0..50 {
+        if database.pruned_height().unwrap() == Some(height.into()) {
+            return
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("The blocks up to the height {height} are not pruned");
+}
+
+#[tokio::test]
+async fn blocks_outside_of_retention_window_are_pruned() {
+    let mut config = Config::local_node();
+    config.manual_blocks_enabled = true;
+    config.block_pruning = Some(pruner::Config::new(2));
+    let database = Database::default();
+    let srv = FuelService::from_database(database.clone(), config)
+        .await
+        .unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let tx = Transaction::default_test_tx();
+    let tx_id = tx.id(&ConsensusParameters::DEFAULT.chain_id);
+    client.submit_and_await_commit(&tx).await.unwrap();
+    client.produce_blocks(3, None).await.unwrap();
+    wait_for_pruned_height(&database, 2).await;
+
+    // The pruned block is reported as pruned instead of not found.
+    let err = client.block_by_height(1).await.unwrap_err();
+    assert!(err.to_string().contains("pruned"), "{err}");
+    assert!(client.transaction(&tx_id).await.unwrap().is_none());
+
+    // The blocks within the retention window are available.
+    let block = client.block_by_height(3).await.unwrap().unwrap();
+    assert_eq!(block.header.height, 3);
+    let latest = client.chain_info().await.unwrap().latest_block;
+    assert_eq!(latest.header.height, 4);
+}
+
+#[tokio::test]
+async fn blocks_are_kept_without_pruning() {
+    let mut config = Config::local_node();
+    config.manual_blocks_enabled = true;
+    let database = Database::default();
+    let srv = FuelService::from_database(database.clone(), config)
+        .await
+        .unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let tx = Transaction::default_test_tx();
+    let tx_id = tx.id(&ConsensusParameters::DEFAULT.chain_id);
+    client.submit_and_await_commit(&tx).await.unwrap();
+    client.produce_blocks(3, None).await.unwrap();
+
+    assert_eq!(database.pruned_height().unwrap(), None);
+    assert!(client.block_by_height(1).await.unwrap().is_some());
+    assert!(client.transaction(&tx_id).await.unwrap().is_some());
+}
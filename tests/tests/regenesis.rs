@@ -0,0 +1,187 @@
+use crate::helpers::transfer;
+use fuel_core::{
+    chain_config::{
+        ChainConfigDb,
+        CoinConfig,
+        ContractConfig,
+        LastBlockConfig,
+        MessageConfig,
+        StateConfig,
+        StateEntry,
+        StateWriter,
+    },
+    database::Database,
+    service::{
+        genesis::verify_regenesis,
+        Config,
+        FuelService,
+        ServiceTrait,
+    },
+};
+use fuel_core_client::client::FuelClient;
+use fuel_core_storage::{
+    tables::FuelBlocks,
+    StorageAsRef,
+};
+use fuel_core_types::{
+    blockchain::primitives::DaBlockHeight,
+    fuel_types::{
+        BlockHeight,
+        Nonce,
+        *,
+    },
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use std::path::Path;
+use tempfile::NamedTempFile;
+
+fn source_state(rng: &mut StdRng) -> StateConfig {
+    StateConfig {
+        height: Some(BlockHeight::from(10)),
+        coins: Some(
+            [100, 200]
+                .into_iter()
+                .map(|amount| CoinConfig {
+                    tx_id: Some(rng.gen()),
+                    output_index: Some(rng.gen()),
+                    tx_pointer_block_height: Some(BlockHeight::from(7)),
+                    tx_pointer_tx_idx: Some(rng.gen()),
+                    maturity: None,
+                    owner: Address::default(),
+                    amount,
+                    asset_id: AssetId::BASE,
+                })
+                .collect(),
+        ),
+        contracts: Some(vec![ContractConfig {
+            contract_id: [11; 32].into(),
+            code: vec![8; 32],
+            salt: Salt::new([9; 32]),
+            state: Some(vec![(Bytes32::new([5u8; 32]), Bytes32::new([8u8; 32]))]),
+            balances: Some(vec![(AssetId::new([3u8; 32]), 100)]),
+            tx_id: Some(rng.gen()),
+            output_index: Some(rng.gen()),
+            tx_pointer_block_height: Some(BlockHeight::from(10)),
+            tx_pointer_tx_idx: Some(rng.gen()),
+        }]),
+        messages: Some(vec![MessageConfig {
+            sender: rng.gen(),
+            recipient: rng.gen(),
+            nonce: Nonce::from(rng.gen_range(0..1000)),
+            amount: rng.gen_range(0..1000),
+            data: vec![],
+            da_height: DaBlockHeight(rng.gen_range(0..1000)),
+        }]),
+    }
+}
+
+fn write_state<I>(path: &Path, entries: I)
+where
+    I: Iterator<Item = fuel_core_storage::Result<StateEntry>>,
+{
+    let mut writer = StateWriter::new(std::fs::File::create(path).unwrap());
+    for entry in entries {
+        writer.write(&entry.unwrap()).unwrap();
+    }
+    writer.finish().unwrap();
+}
+
+async fn start_from_state(path: &Path) -> (FuelService, Database) {
+    let db = Database::default();
+    let mut config = Config::local_node();
+    config.chain_conf.initial_state = None;
+    config.genesis_state = Some(path.to_path_buf());
+    let srv = FuelService::from_database(db.clone(), config)
+        .await
+        .unwrap();
+    (srv, db)
+}
+
+#[tokio::test]
+async fn regenesis_continues_the_chain_from_the_snapshot_height() {
+    let mut rng = StdRng::seed_from_u64(1234);
+    let owner = Address::default();
+    let recipient = Address::new([1; 32]);
+
+    // the source chain produces a block and stops
+    let db = Database::default();
+    let mut config = Config::local_node();
+    config.chain_conf.initial_state = Some(source_state(&mut rng));
+    let srv = FuelService::from_database(db.clone(), config)
+        .await
+        .unwrap();
+    transfer(&FuelClient::from(srv.bound_address), &owner, recipient, 150).await;
+    srv.stop_and_await().await.unwrap();
+    let last_height = db.latest_height().unwrap();
+    assert_eq!(last_height, BlockHeight::from(11));
+
+    // the exported state starts with the last block of the source chain
+    let file = NamedTempFile::new().unwrap();
+    write_state(file.path(), db.regenesis_snapshot().unwrap());
+    let blocks_root = db
+        .storage::<FuelBlocks>()
+        .root(&last_height)
+        .unwrap()
+        .into();
+    let entries = db.regenesis_snapshot().unwrap().collect::<Vec<_>>();
+    assert_eq!(
+        entries[0].as_ref().unwrap(),
+        &StateEntry::LastBlock(LastBlockConfig {
+            height: last_height,
+            blocks_root,
+        })
+    );
+
+    // the genesis of the new chain follows the last block and links to its blocks
+    let (new_srv, new_db) = start_from_state(file.path()).await;
+    let genesis_height = new_db.genesis_height().unwrap();
+    assert_eq!(genesis_height, BlockHeight::from(12));
+    let genesis = new_db
+        .storage::<FuelBlocks>()
+        .get(&genesis_height)
+        .unwrap()
+        .unwrap()
+        .into_owned();
+    assert_eq!(genesis.header().prev_root(), &blocks_root);
+    verify_regenesis(&db, &new_db).unwrap();
+
+    // the utxo ids and the transaction pointers are preserved
+    let state = StateConfig::generate_state_config(db.clone()).unwrap();
+    let new_state = StateConfig::generate_state_config(new_db.clone()).unwrap();
+    assert_eq!(new_state.coins, state.coins);
+    assert_eq!(new_state.contracts, state.contracts);
+    assert_eq!(new_state.messages, state.messages);
+
+    // the new chain produces the next block on top of the genesis
+    transfer(
+        &FuelClient::from(new_srv.bound_address),
+        &owner,
+        recipient,
+        10,
+    )
+    .await;
+    assert_eq!(new_db.latest_height().unwrap(), BlockHeight::from(13));
+}
+
+#[tokio::test]
+async fn regenesis_verification_rejects_a_chain_not_following_the_source() {
+    let mut rng = StdRng::seed_from_u64(1234);
+    let db = Database::default();
+    let mut config = Config::local_node();
+    config.chain_conf.initial_state = Some(source_state(&mut rng));
+    let srv = FuelService::from_database(db.clone(), config)
+        .await
+        .unwrap();
+    srv.stop_and_await().await.unwrap();
+
+    // the same state without the last block starts a new chain at the same height
+    let file = NamedTempFile::new().unwrap();
+    write_state(file.path(), db.state_entries());
+    let (_, new_db) = start_from_state(file.path()).await;
+
+    assert!(verify_regenesis(&db, &new_db).is_err());
+}
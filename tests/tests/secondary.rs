@@ -0,0 +1,87 @@
+use fuel_core::service::{
+    secondary::Config as SecondaryConfig,
+    Config,
+    DbType,
+    FuelService,
+};
+use fuel_core_client::client::FuelClient;
+use fuel_core_types::fuel_tx::*;
+use std::time::Duration;
+use tempfile::TempDir;
+
+async fn latest_height(client: &FuelClient) -> u32 {
+    client
+        .chain_info()
+        .await
+        .unwrap()
+        .latest_block
+        .header
+        .height
+}
+
+#[tokio::test]
+async fn secondary_node_serves_the_blocks_of_the_primary_node() {
+    let tmp_dir = TempDir::new().unwrap();
+    let mut config = Config::local_node();
+    config.manual_blocks_enabled = true;
+    config.database_type = DbType::RocksDb;
+    config.database_path = tmp_dir.path().to_path_buf();
+    let primary = FuelService::new_node(config.clone()).await.unwrap();
+    let primary_client = FuelClient::from(primary.bound_address);
+
+    config.read_only_secondary = Some(SecondaryConfig {
+        catch_up_interval: Duration::from_millis(10),
+    });
+    let secondary = FuelService::new_node(config).await.unwrap();
+    let secondary_client = FuelClient::from(secondary.bound_address);
+    assert_eq!(latest_height(&secondary_client).await, 0);
+
+    let tx = Transaction::default_test_tx();
+    let tx_id = tx.id(&ConsensusParameters::DEFAULT.chain_id);
+    primary_client.submit_and_await_commit(&tx).await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while latest_height(&secondary_client).await != 1 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("The secondary node should catch up with the primary node");
+    assert!(secondary_client
+        .transaction(&tx_id)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn secondary_node_rejects_transactions() {
+    let tmp_dir = TempDir::new().unwrap();
+    let mut config = Config::local_node();
+    config.database_type = DbType::RocksDb;
+    config.database_path = tmp_dir.path().to_path_buf();
+    let _primary = FuelService::new_node(config.clone()).await.unwrap();
+
+    config.read_only_secondary = Some(SecondaryConfig::default());
+    let secondary = FuelService::new_node(config).await.unwrap();
+    let client = FuelClient::from(secondary.bound_address);
+
+    let err = client
+        .submit(&Transaction::default_test_tx())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("serves only the queries"), "{err}");
+    let err = client.produce_blocks(1, None).await.unwrap_err();
+    assert!(err.to_string().contains("Manual Blocks"), "{err}");
+}
+
+#[tokio::test]
+async fn secondary_node_requires_initialized_database() {
+    let tmp_dir = TempDir::new().unwrap();
+    let mut config = Config::local_node();
+    config.database_type = DbType::RocksDb;
+    config.database_path = tmp_dir.path().to_path_buf();
+    config.read_only_secondary = Some(SecondaryConfig::default());
+
+    assert!(FuelService::new_node(config).await.is_err());
+}
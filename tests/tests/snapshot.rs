@@ -1,16 +1,24 @@
+use crate::helpers::transfer;
 use fuel_core::{
     chain_config::{
+        ChainConfigDb,
         CoinConfig,
         ContractConfig,
         MessageConfig,
         StateConfig,
+        StateEntry,
+        StateWriter,
+    },
+    database::{
+        snapshot::SnapshotFilter,
+        Database,
     },
-    database::Database,
     service::{
         Config,
         FuelService,
     },
 };
+use fuel_core_client::client::FuelClient;
 use fuel_core_types::{
     blockchain::primitives::DaBlockHeight,
     fuel_types::{
@@ -19,22 +27,17 @@ use fuel_core_types::{
         *,
     },
 };
+use itertools::Itertools;
 use rand::{
     rngs::StdRng,
     Rng,
     SeedableRng,
 };
 
-#[tokio::test]
-async fn snapshot_state_config() {
-    let mut rng = StdRng::seed_from_u64(1234);
-    let db = Database::default();
-
+fn starting_state(rng: &mut StdRng) -> StateConfig {
     let owner = Address::default();
 
-    // setup config
-    let mut config = Config::local_node();
-    let starting_state = StateConfig {
+    StateConfig {
         height: Some(BlockHeight::from(10)),
         contracts: Some(vec![ContractConfig {
             contract_id: [11; 32].into(),
@@ -80,7 +83,17 @@ async fn snapshot_state_config() {
             data: vec![],
             da_height: DaBlockHeight(rng.gen_range(0..1000)),
         }]),
-    };
+    }
+}
+
+#[tokio::test]
+async fn snapshot_state_config() {
+    let mut rng = StdRng::seed_from_u64(1234);
+    let db = Database::default();
+
+    // setup config
+    let mut config = Config::local_node();
+    let starting_state = starting_state(&mut rng);
 
     config.chain_conf.initial_state = Some(starting_state.clone());
 
@@ -116,3 +129,184 @@ async fn snapshot_state_config() {
 
     assert_eq!(state_conf.messages, starting_state.messages)
 }
+
+#[tokio::test]
+async fn streamed_snapshot_initializes_the_same_state() {
+    let mut rng = StdRng::seed_from_u64(1234);
+    let db = Database::default();
+    let mut config = Config::local_node();
+    config.chain_conf.initial_state = Some(starting_state(&mut rng));
+    let _ = FuelService::from_database(db.clone(), config)
+        .await
+        .unwrap();
+
+    // dump the state entry by entry
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let mut writer = StateWriter::new(file.reopen().unwrap());
+    for entry in db.state_entries() {
+        writer.write(&entry.unwrap()).unwrap();
+    }
+    writer.finish().unwrap();
+
+    // start a new node from the dumped state
+    let new_db = Database::default();
+    let mut config = Config::local_node();
+    config.chain_conf.initial_state = None;
+    config.genesis_state = Some(file.path().to_path_buf());
+    let _ = FuelService::from_database(new_db.clone(), config)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        StateConfig::generate_state_config(new_db.clone()).unwrap(),
+        StateConfig::generate_state_config(db.clone()).unwrap()
+    );
+    let genesis = db.get_genesis().unwrap();
+    let new_genesis = new_db.get_genesis().unwrap();
+    assert_eq!(new_genesis.coins_root, genesis.coins_root);
+    assert_eq!(new_genesis.contracts_root, genesis.contracts_root);
+    assert_eq!(new_genesis.messages_root, genesis.messages_root);
+}
+
+fn coins_of(entries: &[StateEntry]) -> Vec<(Address, AssetId, u64)> {
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            StateEntry::Coin(coin) => Some((coin.owner, coin.asset_id, coin.amount)),
+            _ => None,
+        })
+        .sorted()
+        .collect()
+}
+
+#[tokio::test]
+async fn snapshot_filters_the_state() {
+    let mut rng = StdRng::seed_from_u64(1234);
+    let db = Database::default();
+    let mut config = Config::local_node();
+    let state = starting_state(&mut rng);
+    let contract = state.contracts.as_ref().unwrap()[0].clone();
+    config.chain_conf.initial_state = Some(state);
+    let _ = FuelService::from_database(db.clone(), config)
+        .await
+        .unwrap();
+
+    let snapshot = |filter| {
+        db.state_snapshot(None, filter)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    };
+
+    // only the coins and balances of the asset are included
+    let asset_id = AssetId::new([3u8; 32]);
+    let entries = snapshot(SnapshotFilter {
+        asset_ids: vec![asset_id],
+        ..Default::default()
+    });
+    assert_eq!(
+        coins_of(&entries),
+        vec![(Address::default(), asset_id, 100)]
+    );
+    let balances = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            StateEntry::ContractBalance(balance) => Some(balance.asset_id),
+            _ => None,
+        })
+        .collect_vec();
+    assert_eq!(balances, vec![asset_id]);
+
+    // the messages and the coins of other owners are excluded
+    let entries = snapshot(SnapshotFilter {
+        owners: vec![Address::new([1; 32])],
+        contract_ids: vec![contract.contract_id],
+        ..Default::default()
+    });
+    assert!(coins_of(&entries).is_empty());
+    assert!(!entries
+        .iter()
+        .any(|entry| matches!(entry, StateEntry::Message(_))));
+    let contracts = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            StateEntry::Contract(contract) => Some(contract.contract_id),
+            _ => None,
+        })
+        .collect_vec();
+    assert_eq!(contracts, vec![contract.contract_id]);
+
+    // the unknown contract is an error
+    let result = db
+        .state_snapshot(
+            None,
+            SnapshotFilter {
+                contract_ids: vec![ContractId::new([1; 32])],
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>();
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn snapshot_at_past_height_is_read_from_the_archive() {
+    let owner = Address::default();
+    let recipient = Address::new([1; 32]);
+    let db = Database::default();
+    let mut config = Config::local_node();
+    config.chain_conf.initial_state = Some(StateConfig {
+        coins: Some(
+            [100, 200]
+                .into_iter()
+                .map(|amount| CoinConfig {
+                    tx_id: None,
+                    output_index: None,
+                    tx_pointer_block_height: None,
+                    tx_pointer_tx_idx: None,
+                    maturity: None,
+                    owner,
+                    amount,
+                    asset_id: AssetId::BASE,
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    });
+    config.archive = true;
+    let srv = FuelService::from_database(db.clone(), config)
+        .await
+        .unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    transfer(&client, &owner, recipient, 150).await;
+
+    let snapshot = |height: Option<u32>, owners| {
+        db.state_snapshot(
+            height.map(Into::into),
+            SnapshotFilter {
+                owners,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+    };
+    let genesis = snapshot(Some(0), vec![]);
+    assert_eq!(genesis[0], StateEntry::Height(0u32.into()));
+    assert_eq!(
+        coins_of(&genesis),
+        vec![(owner, AssetId::BASE, 100), (owner, AssetId::BASE, 200)]
+    );
+    assert!(coins_of(&snapshot(Some(0), vec![recipient])).is_empty());
+
+    let latest = snapshot(None, vec![recipient]);
+    assert_eq!(latest[0], StateEntry::Height(1u32.into()));
+    assert_eq!(coins_of(&latest), vec![(recipient, AssetId::BASE, 150)]);
+    assert_eq!(
+        coins_of(&snapshot(Some(1), vec![recipient])),
+        coins_of(&latest)
+    );
+}